@@ -0,0 +1,673 @@
+#![allow(unused_imports, non_snake_case, non_camel_case_types, non_upper_case_globals, deprecated)]
+use std::collections::BTreeMap as Map;
+use serde::{Serialize, Deserialize};
+use serde_bytes::ByteBuf as Bytes;
+use tfschema_bindgen::runtime::{Change, Count, Dynamic, Expr, ForEach, IgnoreChanges, ProviderRef, Sensitive, Set};
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct config {
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_roots")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Vec<data_root>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_roots")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<Vec<provider_root>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_roots")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<Vec<resource_root>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_object")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locals: Option<Map<String, Dynamic>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_labels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module: Option<Map<String, module>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_labels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<Map<String, output>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_labels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variable: Option<Map<String, variable>>,
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform: Option<terraform>,
+}
+
+
+/// How provisioners connect to the remote resource.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct connection {
+
+    /// Whether to use the SSH agent for authentication.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<bool>,
+
+    /// Preferred identity of the SSH agent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent_identity: Option<String>,
+
+    /// Contents of a signed CA certificate, used along with `bastion_private_key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_certificate: Option<String>,
+
+    /// Bastion host to connect through.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_host: Option<String>,
+
+    /// Public key of the bastion host or of its signing CA.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_host_key: Option<String>,
+
+    /// Password of the bastion user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_password: Option<String>,
+
+    /// Port of the bastion host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_port: Option<i64>,
+
+    /// Contents of the SSH key to use for the bastion host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_private_key: Option<String>,
+
+    /// User to connect to the bastion host as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bastion_user: Option<String>,
+
+    /// CA certificate to validate against (WinRM).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cacert: Option<String>,
+
+    /// Contents of a signed CA certificate, used along with `private_key`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate: Option<String>,
+
+    /// Address of the resource to connect to.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub host: String,
+
+    /// Public key of the remote host or of its signing CA.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub host_key: Option<String>,
+
+    /// Whether to connect using HTTPS (WinRM).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub https: Option<bool>,
+
+    /// Whether to skip validating the HTTPS certificate chain (WinRM).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub insecure: Option<bool>,
+
+    /// Password of the user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    /// Port to connect to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<i64>,
+
+    /// Contents of the SSH key to use.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+
+    /// Proxy host to connect through.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_host: Option<String>,
+
+    /// Port of the proxy host.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_port: Option<i64>,
+
+    /// Scheme of the proxy: `http`, `https`, `socks5`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_scheme: Option<String>,
+
+    /// User of the proxy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_user_name: Option<String>,
+
+    /// Password of the proxy user.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proxy_user_password: Option<String>,
+
+    /// Path where scripts are copied to on the remote resource.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_path: Option<String>,
+
+    /// Platform of the remote resource: `unix` (default) or `windows`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_platform: Option<String>,
+
+    /// Time to wait for the connection to be available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+
+    /// Connection type: `ssh` (default) or `winrm`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+
+    /// Whether to use NTLM authentication (WinRM).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_ntlm: Option<bool>,
+
+    /// User to connect as.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+
+/// Decoded `change` of a `data` mode item, keyed by type.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum data_change_values {
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum data_root {
+}
+
+
+/// Decoded `values` of a `data` mode item, keyed by type.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum data_values {
+}
+
+
+/// Call of a child module, keyed by name.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct module {
+
+    /// Dependencies not visible from the inputs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+
+    /// Location of the module source code.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub source: String,
+
+    /// Version constraint of a registry module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Number of instances to create.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<Count>,
+
+    /// Instances to create, one per element of a set or a map.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub for_each: Option<ForEach>,
+
+    /// Provider configurations passed to the module, keyed by their name in the module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub providers: Option<Map<String, ProviderRef>>,
+
+    /// Input variables of the module.
+    #[serde(flatten)]
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub inputs: Map<String, Dynamic>,
+}
+
+
+/// Output value of the configuration, keyed by name.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct output {
+
+    /// Dependencies not visible from `value`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depends_on: Option<Vec<String>>,
+
+    /// Documentation of the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether the value is hidden from the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+
+    /// Value of the output.
+    pub value: Dynamic,
+}
+
+
+/// Plan document, as printed by `terraform show -json`.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct plan {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub format_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub planned_values: Option<state_values>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resource_changes: Vec<plan_resource_change>,
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub output_changes: Map<String, plan_change>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prior_state: Option<state>,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct plan_change {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub actions: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Dynamic>,
+
+    /// Values after the change, without those only known after apply.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Dynamic>,
+
+    /// Mask of `after`, where values only known after apply are replaced by `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_unknown: Option<Dynamic>,
+
+    /// Mask of `before`, where sensitive values are replaced by `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before_sensitive: Option<Dynamic>,
+
+    /// Mask of `after`, where sensitive values are replaced by `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after_sensitive: Option<Dynamic>,
+}
+
+impl plan_change {
+    /// Decodes `before` and `after` into the given generated definition. Values only known
+    /// after apply are left out of `after` (see `after_unknown`), so they decode as `None`
+    /// into the `_state` structs, where every attribute is optional.
+    pub fn decode<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<Change<T>, tfschema_bindgen::runtime::DecodeError> {
+        Ok(Change {
+            before: self.before.as_ref().map(Dynamic::decode).transpose()?,
+            after: self.after.as_ref().map(Dynamic::decode).transpose()?,
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct plan_resource_change {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub address: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub module_address: Option<String>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub mode: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub r#type: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<Dynamic>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub provider_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deposed: Option<String>,
+
+    /// Planned change, see `decode_resource` and `decode_data`.
+    pub change: plan_change,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub action_reason: Option<String>,
+}
+
+impl plan_resource_change {
+    /// Decodes `change` into the generated definition of the item type, or returns `None`
+    /// if the item is not a `managed` mode item of a known type.
+    pub fn decode_resource(&self) -> Option<Result<resource_change_values, tfschema_bindgen::runtime::DecodeError>> {
+        match (self.mode.as_str(), self.r#type.as_str()) {
+            _ => None,
+        }
+    }
+
+    /// Decodes `change` into the generated definition of the item type, or returns `None`
+    /// if the item is not a `data` mode item of a known type.
+    pub fn decode_data(&self) -> Option<Result<data_change_values, tfschema_bindgen::runtime::DecodeError>> {
+        match (self.mode.as_str(), self.r#type.as_str()) {
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum provider_root {
+    #[serde(deserialize_with = "tfschema_bindgen::runtime::deserialize_blocks")]
+    test_provider(Vec<test_provider_details>),
+}
+
+
+/// Provisioner of a resource, by type.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum provisioner {
+    #[serde(rename = "local-exec", deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    local_exec(provisioner_local_exec),
+    #[serde(rename = "remote-exec", deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    remote_exec(provisioner_remote_exec),
+    #[serde(rename = "file", deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    file(provisioner_file),
+}
+
+
+/// Copies files or directories to the remote resource.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct provisioner_file {
+
+    /// Content to copy, instead of `source`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+
+    /// Destination path on the remote resource.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub destination: String,
+
+    /// Whether to `fail` (default) or `continue` on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+
+    /// Local file or directory to copy.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// When to run: `create` (default) or `destroy`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// How provisioners connect to the remote resource.
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<connection>,
+}
+
+
+/// Runs a command on the machine running Terraform.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct provisioner_local_exec {
+
+    /// Command to run.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub command: String,
+
+    /// Environment variables of the command.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub environment: Option<Map<String, String>>,
+
+    /// Interpreter (and its arguments) the command is passed to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interpreter: Option<Vec<String>>,
+
+    /// Whether to `fail` (default) or `continue` on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+
+    /// Whether to hide the command from the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quiet: Option<bool>,
+
+    /// When to run: `create` (default) or `destroy`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// Directory the command is run in.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub working_dir: Option<String>,
+
+    /// How provisioners connect to the remote resource.
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<connection>,
+}
+
+
+/// Runs commands on the remote resource.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct provisioner_remote_exec {
+
+    /// Commands to run, in order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub inline: Option<Vec<String>>,
+
+    /// Whether to `fail` (default) or `continue` on failure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_failure: Option<String>,
+
+    /// Path of a local script copied to the resource and run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+
+    /// Paths of local scripts copied to the resource and run, in order.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scripts: Option<Vec<String>>,
+
+    /// When to run: `create` (default) or `destroy`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub when: Option<String>,
+
+    /// How provisioners connect to the remote resource.
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_block")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connection: Option<connection>,
+}
+
+
+/// Decoded `change` of a `managed` mode item, keyed by type.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum resource_change_values {
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum resource_root {
+}
+
+
+/// Decoded `values` of a `managed` mode item, keyed by type.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub enum resource_values {
+}
+
+
+/// State document, as printed by `terraform show -json`.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct state {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub format_version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub terraform_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<state_values>,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct state_module {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resources: Vec<state_resource>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub child_modules: Vec<state_module>,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct state_output {
+    pub value: Dynamic,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<Dynamic>,
+    pub sensitive: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct state_resource {
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub address: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub mode: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub r#type: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index: Option<Dynamic>,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub provider_name: String,
+    pub schema_version: i64,
+
+    /// Attribute values, see `decode_resource` and `decode_data`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Dynamic>,
+
+    /// Mask of `values`, where sensitive values are replaced by `true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive_values: Option<Dynamic>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+}
+
+impl state_resource {
+    /// Decodes `values` into the generated definition of the item type, or returns `None`
+    /// if the item is not a `managed` mode item of a known type.
+    pub fn decode_resource(&self) -> Option<Result<resource_values, tfschema_bindgen::runtime::DecodeError>> {
+        match (self.mode.as_str(), self.r#type.as_str()) {
+            _ => None,
+        }
+    }
+
+    /// Decodes `values` into the generated definition of the item type, or returns `None`
+    /// if the item is not a `data` mode item of a known type.
+    pub fn decode_data(&self) -> Option<Result<data_values, tfschema_bindgen::runtime::DecodeError>> {
+        match (self.mode.as_str(), self.r#type.as_str()) {
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct state_values {
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub outputs: Map<String, state_output>,
+    pub root_module: state_module,
+}
+
+
+/// Settings of Terraform itself.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct terraform {
+
+    /// Version constraint of Terraform.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_version: Option<String>,
+
+    /// Providers required by the module, keyed by their local name.
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_labels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub required_providers: Option<Map<String, terraform_required_provider>>,
+
+    /// Backend storing the state, keyed by its type (e.g. `s3`).
+    #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_labels")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub backend: Option<Map<String, Dynamic>>,
+}
+
+impl terraform {
+    /// Settings requiring the providers these definitions were generated for.
+    pub fn with_generated_providers() -> Self {
+        let required_providers = vec![
+            ("test_provider".to_owned(), terraform_required_provider {
+                source: None,
+                ..Default::default()
+            }),
+        ];
+        terraform {
+            required_providers: Some(required_providers.into_iter().collect()),
+            ..Default::default()
+        }
+    }
+}
+
+
+/// Requirement of a provider, keyed by its local name.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct terraform_required_provider {
+
+    /// Source address of the provider (e.g. `hashicorp/aws`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// Version constraint of the provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+
+    /// Aliased configurations of the provider expected by a module.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub configuration_aliases: Option<Vec<ProviderRef>>,
+}
+
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct test_provider_details {
+
+    /// Name of an additional configuration of the provider, referenced as `{provider}.{alias}`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+
+    /// The url.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+impl tfschema_bindgen::runtime::Resource for test_provider_details {
+    const TYPE_NAME: &'static str = "test_provider";
+    const KIND: tfschema_bindgen::runtime::ResourceKind = tfschema_bindgen::runtime::ResourceKind::Provider;
+    const PROVIDER: &'static str = "test_provider";
+    const SCHEMA_VERSION: i64 = 0;
+    const ATTRIBUTES: &'static [tfschema_bindgen::runtime::AttributeSchema] = &[
+        tfschema_bindgen::runtime::AttributeSchema {
+            name: "base_url",
+            required: false,
+            optional: true,
+            computed: false,
+            sensitive: false,
+            deprecated: false,
+        },
+    ];
+}
+
+
+/// Input variable of the configuration, keyed by name.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+pub struct variable {
+
+    /// Value used when none is provided.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub default: Option<Dynamic>,
+
+    /// Documentation of the variable.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+
+    /// Whether the value may be `null`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nullable: Option<bool>,
+
+    /// Whether the value is hidden from the output.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sensitive: Option<bool>,
+
+    /// Type constraint of the value (e.g. `list(string)`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r#type: Option<String>,
+
+    /// Rule values of the variable must follow.
+    #[serde(deserialize_with = "tfschema_bindgen::runtime::deserialize_blocks")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub validation: Vec<variable_block_type_validation>,
+}
+
+
+/// Rule values of the variable must follow.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
+#[serde(rename = "validation")]
+pub struct variable_block_type_validation {
+
+    /// Expression which must be true.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub condition: String,
+
+    /// Error reported when the condition is false.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub error_message: String,
+}
+
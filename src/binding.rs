@@ -1,5 +1,5 @@
 use crate::config::CodeGeneratorConfig;
-use crate::emit::{CodeGenerator, Registry};
+use crate::emit::{quote_type, CodeGenerator, QualifiedName, Registry};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use serde_reflection::{ContainerFormat, Format, Named, VariantFormat};
@@ -44,8 +44,8 @@ pub const RESERVED_WORDS: [&str; 32] = [
     "box",
 ];
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-enum Void {}
+/// Base of links relative to the Terraform website found in markdown descriptions.
+const TERRAFORM_WEBSITE_URL: &str = "https://www.terraform.io";
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct TerraformSchemaExport {
@@ -63,6 +63,8 @@ pub struct Schema {
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Provider {
     version: i64,
+    // left out of the schema of providers without configuration (e.g. `terraform`)
+    #[serde(default)]
     block: Block,
 }
 
@@ -76,6 +78,9 @@ pub struct SchemaItem {
 pub struct Block {
     attributes: Option<BTreeMap<String, Attribute>>,
     block_types: Option<BTreeMap<String, NestedBlock>>,
+    description: Option<String>,
+    description_kind: Option<StringKind>,
+    deprecated: Option<bool>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -87,7 +92,9 @@ pub enum StringKind {
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct Attribute {
+    #[serde(default)]
     r#type: AttributeType,
+    nested_type: Option<NestedType>,
     description: Option<String>,
     required: Option<bool>,
     optional: Option<bool>,
@@ -95,6 +102,10 @@ pub struct Attribute {
     sensitive: Option<bool>,
     description_kind: Option<StringKind>,
     deprecated: Option<bool>,
+    /// Whether values must be literals, as Terraform requires of meta-arguments and settings
+    /// (never wrapped in `Expr`).
+    #[serde(skip)]
+    literal: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Default)]
@@ -105,6 +116,14 @@ pub struct NestedBlock {
     max_items: Option<u16>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct NestedType {
+    attributes: BTreeMap<String, Attribute>,
+    nesting_mode: Option<String>,
+    min_items: Option<u8>,
+    max_items: Option<u16>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 struct AttributeType(Value);
 
@@ -115,22 +134,48 @@ pub fn generate_serde(
 ) -> std::result::Result<(), Box<dyn std::error::Error>> {
     let config = CodeGeneratorConfig::new(config.to_string());
 
-    CodeGenerator::new(&config).output(out, registry)
+    generate_serde_with_config(&config, out, registry)
+}
+
+/// Generate code for the registry, attaching the comments collected in `config`.
+pub fn generate_serde_with_config(
+    config: &CodeGeneratorConfig,
+    out: &mut dyn Write,
+    registry: &Registry,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    CodeGenerator::new(config).output(out, registry)
 }
 
 pub fn export_schema_to_registry(
     schema: &TerraformSchemaExport,
+) -> std::result::Result<Registry, Box<dyn std::error::Error>> {
+    let mut config = CodeGeneratorConfig::new(String::new());
+
+    export_schema_with_config(schema, &mut config)
+}
+
+/// Export the schema to a registry, recording the documentation of generated definitions
+/// into `config`.
+pub fn export_schema_with_config(
+    schema: &TerraformSchemaExport,
+    config: &mut CodeGeneratorConfig,
 ) -> std::result::Result<Registry, Box<dyn std::error::Error>> {
     let mut r = Registry::new();
     let mut roots = BTreeMap::new();
     roots.insert("provider", Vec::<&str>::new());
     roots.insert("resource", Vec::<&str>::new());
     roots.insert("data", Vec::<&str>::new());
+    let mut sources = BTreeMap::new();
 
-    for (pn, pv) in &schema.provider_schemas {
-        let pn = pn.split('/').last().unwrap_or(pn);
+    for (source, pv) in &schema.provider_schemas {
+        let pn = source.split('/').next_back().unwrap_or(source);
+        sources.insert(pn, source.as_str());
         let ps = &pv.provider;
-        export_block(None, pn, ps.block.clone(), &mut r)?;
+        let mut b = ps.block.clone();
+        inject_provider_alias(&mut b);
+        export_block(None, pn, &format!("{}_details", pn), b, &mut r, config)?;
+        let item = ("Provider", pn, pn, ps.version);
+        export_resource_impl(item, &ps.block, &[format!("{}_details", pn)], config);
         if let Some(provider) = roots.get_mut("provider") {
             provider.push(pn);
         }
@@ -139,9 +184,23 @@ pub fn export_schema_to_registry(
             for (n, i) in rss {
                 // add terraform meta-tags to block
                 let mut b = i.block.clone();
-                inject_meta_arguments(&mut b);
+                inject_meta_arguments(&mut b, true);
 
-                export_block(Some("resource".to_owned()), n, b, &mut r)?;
+                export_schema_item("resource", n, b, &i.block, &mut r, config)?;
+                inject_meta_fields(&input_struct_name(n, config), &mut r, config);
+                inject_provisioners(&input_struct_name(n, config), &mut r, config);
+                export_references("resource", n, &i.block, &r, config);
+                let structs = [
+                    input_struct_name(n, config),
+                    state_struct_name(n, config),
+                    plan_struct_name(n, config),
+                ];
+                let item = ("Resource", n.as_str(), pn, i.version);
+                export_resource_impl(item, &i.block, &structs, config);
+                if i.block.deprecated == Some(true) {
+                    let annotation = deprecated_annotation(i.block.description.as_ref());
+                    config.add_annotation(&["resource_root", n], annotation);
+                }
                 if let Some(resources) = roots.get_mut("resource") {
                     resources.push(n);
                 }
@@ -150,24 +209,62 @@ pub fn export_schema_to_registry(
 
         if let Some(dss) = &pv.data_source_schemas {
             for (n, i) in dss {
-                let b = i.block.clone();
-                export_block(Some("data_source".to_owned()), n, b, &mut r)?;
+                let mut b = i.block.clone();
+                inject_meta_arguments(&mut b, false);
+                export_schema_item("data_source", n, b, &i.block, &mut r, config)?;
+                inject_meta_fields(&input_struct_name(n, config), &mut r, config);
+                export_references("data", n, &i.block, &r, config);
+                let structs = [
+                    input_struct_name(n, config),
+                    state_struct_name(n, config),
+                    plan_struct_name(n, config),
+                ];
+                let item = ("DataSource", n.as_str(), pn, i.version);
+                export_resource_impl(item, &i.block, &structs, config);
+                if i.block.deprecated == Some(true) {
+                    let annotation = deprecated_annotation(i.block.description.as_ref());
+                    config.add_annotation(&["data_root", n], annotation);
+                }
                 if let Some(resources) = roots.get_mut("data") {
                     resources.push(n);
                 }
             }
         }
+    }
 
-        export_roots(&roots, &mut r);
-        generate_config(&roots, &mut r);
+    if config.object_layout {
+        export_object_roots(&roots, &mut r, config);
+    } else {
+        export_roots(&roots, &mut r, config);
     }
+    generate_config(&roots, &mut r, config);
+    generate_config_blocks(&mut r, config)?;
+    generate_terraform_settings(&sources, &mut r, config)?;
+    generate_provisioners(&mut r, config)?;
+    generate_state(&roots, &mut r, config);
+    generate_plan(&roots, &mut r, config);
     Ok(r)
 }
 
-fn generate_config(roots: &BTreeMap<&str, Vec<&str>>, reg: &mut Registry) {
+fn generate_config(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) {
     let mut target_attrs = Vec::new();
 
     for root_name in roots.keys() {
+        if config.object_layout {
+            let annotation = format!(
+                "#[serde(default, deserialize_with = \"tfschema_bindgen::runtime::deserialize_object\", skip_serializing_if = \"{}_root::is_empty\")]",
+                root_name
+            );
+            config.add_annotation(&["config", root_name], annotation);
+            target_attrs.push(field(root_name, type_name(&format!("{}_root", root_name))));
+            continue;
+        }
+        let annotation = deserialize_with("deserialize_roots", true);
+        config.add_annotation(&["config", root_name], annotation);
         target_attrs.push(Named {
             name: root_name.to_string(),
             value: Format::Option(Box::new(Format::Seq(Box::new(Format::TypeName(format!(
@@ -176,21 +273,487 @@ fn generate_config(roots: &BTreeMap<&str, Vec<&str>>, reg: &mut Registry) {
             )))))),
         });
     }
+
+    let blocks = [
+        ("locals", map(type_name("Dynamic")), "deserialize_object"),
+        ("module", map(type_name("module")), "deserialize_labels"),
+        ("output", map(type_name("output")), "deserialize_labels"),
+        ("variable", map(type_name("variable")), "deserialize_labels"),
+    ];
+    for (block_name, value, function) in blocks.iter() {
+        config.add_annotation(&["config", block_name], deserialize_with(function, true));
+        target_attrs.push(field(block_name, optional(value.clone())));
+    }
+    config.add_annotation(
+        &["config", "terraform"],
+        deserialize_with("deserialize_block", true),
+    );
+    target_attrs.push(field("terraform", optional(type_name("terraform"))));
     reg.insert(
         (None, "config".to_string()),
         ContainerFormat::Struct(target_attrs),
     );
 }
 
-fn export_roots(roots: &BTreeMap<&str, Vec<&str>>, reg: &mut Registry) {
+/// Generate the `variable`, `output` and `module` blocks of the config root.
+fn generate_config_blocks(
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let string = || serde_json::json!("string");
+    let boolean = || serde_json::json!("bool");
+    let dynamic = || serde_json::json!("dynamic");
+    let strings = || serde_json::json!(["set", "string"]);
+
+    let mut variable = literal_block(
+        "Input variable of the configuration, keyed by name.",
+        vec![
+            (
+                "type",
+                argument(
+                    string(),
+                    "Type constraint of the value (e.g. `list(string)`).",
+                ),
+            ),
+            (
+                "default",
+                argument(dynamic(), "Value used when none is provided."),
+            ),
+            (
+                "description",
+                argument(string(), "Documentation of the variable."),
+            ),
+            (
+                "sensitive",
+                argument(boolean(), "Whether the value is hidden from the output."),
+            ),
+            (
+                "nullable",
+                argument(boolean(), "Whether the value may be `null`."),
+            ),
+        ],
+    );
+    variable.block_types = Some(
+        vec![(
+            "validation".to_owned(),
+            condition_block("Rule values of the variable must follow."),
+        )]
+        .into_iter()
+        .collect(),
+    );
+    export_block(None, "variable", "variable", variable, reg, config)?;
+
+    let output = literal_block(
+        "Output value of the configuration, keyed by name.",
+        vec![
+            (
+                "value",
+                required_argument(dynamic(), "Value of the output."),
+            ),
+            (
+                "description",
+                argument(string(), "Documentation of the output."),
+            ),
+            (
+                "sensitive",
+                argument(boolean(), "Whether the value is hidden from the output."),
+            ),
+            (
+                "depends_on",
+                argument(strings(), "Dependencies not visible from `value`."),
+            ),
+        ],
+    );
+    export_block(None, "output", "output", output, reg, config)?;
+
+    let module = literal_block(
+        "Call of a child module, keyed by name.",
+        vec![
+            (
+                "source",
+                required_argument(string(), "Location of the module source code."),
+            ),
+            (
+                "version",
+                argument(string(), "Version constraint of a registry module."),
+            ),
+            (
+                "depends_on",
+                argument(strings(), "Dependencies not visible from the inputs."),
+            ),
+        ],
+    );
+    export_block(None, "module", "module", module, reg, config)?;
+    let module_fields = [
+        (
+            "count",
+            optional(type_name("Count")),
+            "Number of instances to create.",
+        ),
+        (
+            "for_each",
+            optional(type_name("ForEach")),
+            "Instances to create, one per element of a set or a map.",
+        ),
+        (
+            "providers",
+            optional(map(type_name("ProviderRef"))),
+            "Provider configurations passed to the module, keyed by their name in the module.",
+        ),
+        (
+            "inputs",
+            map(type_name("Dynamic")),
+            "Input variables of the module.",
+        ),
+    ];
+    for (name, value, description) in module_fields.iter() {
+        config.add_comment(&["module", name], description);
+        if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, "module".to_owned())) {
+            fields.push(field(name, value.clone()));
+        }
+    }
+    config.add_annotation(&["module", "inputs"], "#[serde(flatten)]".to_owned());
+
+    Ok(())
+}
+
+fn field(name: &str, value: Format) -> Named<Format> {
+    Named {
+        name: field_name(name),
+        value,
+    }
+}
+
+fn optional(format: Format) -> Format {
+    Format::Option(Box::new(format))
+}
+
+fn map(value: Format) -> Format {
+    Format::Map {
+        key: Box::new(Format::Str),
+        value: Box::new(value),
+    }
+}
+
+fn type_name(name: &str) -> Format {
+    Format::TypeName(name.to_owned())
+}
+
+/// Generated struct holding the arguments of a resource or data source, as found in config.
+fn input_struct_name(name: &str, config: &CodeGeneratorConfig) -> String {
+    if config.separate_state {
+        format!("{}_config", name)
+    } else {
+        format!("{}_details", name)
+    }
+}
+
+/// Generated struct holding the attributes of a resource or data source as found in state.
+fn state_struct_name(name: &str, config: &CodeGeneratorConfig) -> String {
+    if config.separate_state {
+        format!("{}_state", name)
+    } else {
+        format!("{}_details", name)
+    }
+}
+
+/// Generated struct holding the attributes of a resource or data source as found in plans,
+/// where values only known after apply are left out: the state struct with every attribute
+/// optional, generated in both modes.
+fn plan_struct_name(name: &str, _config: &CodeGeneratorConfig) -> String {
+    format!("{}_state", name)
+}
+
+/// Generate the model of state documents printed by `terraform show -json`, along with the
+/// `resource_values` and `data_values` enums which `values` of known resources and data
+/// sources are decoded into.
+fn generate_state(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) {
+    let dynamic = || type_name("Dynamic");
+    reg.insert(
+        (None, "state".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("format_version", Format::Str),
+            field("terraform_version", optional(Format::Str)),
+            field("values", optional(type_name("state_values"))),
+        ]),
+    );
+    config.add_comment(
+        &["state"],
+        "State document, as printed by `terraform show -json`.",
+    );
+    reg.insert(
+        (None, "state_values".to_owned()),
+        ContainerFormat::Struct(vec![
+            field(
+                "outputs",
+                Format::Map {
+                    key: Box::new(Format::Str),
+                    value: Box::new(type_name("state_output")),
+                },
+            ),
+            field("root_module", type_name("state_module")),
+        ]),
+    );
+    reg.insert(
+        (None, "state_output".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("value", dynamic()),
+            field("type", optional(dynamic())),
+            field("sensitive", Format::Bool),
+        ]),
+    );
+    reg.insert(
+        (None, "state_module".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("address", optional(Format::Str)),
+            field(
+                "resources",
+                Format::Seq(Box::new(type_name("state_resource"))),
+            ),
+            field(
+                "child_modules",
+                Format::Seq(Box::new(type_name("state_module"))),
+            ),
+        ]),
+    );
+    reg.insert(
+        (None, "state_resource".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("address", Format::Str),
+            field("mode", Format::Str),
+            field("type", Format::Str),
+            field("name", Format::Str),
+            field("index", optional(dynamic())),
+            field("provider_name", Format::Str),
+            field("schema_version", Format::I64),
+            field("values", optional(dynamic())),
+            field("sensitive_values", optional(dynamic())),
+            field("depends_on", Format::Seq(Box::new(Format::Str))),
+        ]),
+    );
+    config.add_comment(
+        &["state_resource", "values"],
+        "Attribute values, see `decode_resource` and `decode_data`.",
+    );
+    config.add_comment(
+        &["state_resource", "sensitive_values"],
+        "Mask of `values`, where sensitive values are replaced by `true`.",
+    );
+
+    export_decoders(
+        roots,
+        reg,
+        config,
+        ("state_resource", "values"),
+        ("values", state_struct_name),
+        "self.values.clone().unwrap_or_default().decode()",
+        |t| t.to_owned(),
+    );
+}
+
+/// Generate the `decode_resource` and `decode_data` methods of `container`, which decode its
+/// `field` with the `decode` expression after the `mode` and `type` of the item, into the
+/// `resource_{suffix}` and `data_{suffix}` enums. Variants of the enums hold a `payload` of
+/// the generated struct of their type named by `struct_name`.
+fn export_decoders(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+    (container, field): (&str, &str),
+    (suffix, struct_name): (&str, fn(&str, &CodeGeneratorConfig) -> String),
+    decode: &str,
+    payload: fn(&str) -> String,
+) {
+    let decoders = [
+        ("resource", "managed", "decode_resource"),
+        ("data", "data", "decode_data"),
+    ];
+    let mut code = Vec::new();
+    for (root_name, mode, method) in decoders.iter() {
+        let enum_name = format!("{}_{}", root_name, suffix);
+        let members = roots.get(root_name).cloned().unwrap_or_default();
+        let variants = members
+            .iter()
+            .enumerate()
+            .map(|(pos, member)| {
+                let value = type_name(&payload(&struct_name(member, config)));
+                let variant = Named {
+                    name: member.to_string(),
+                    value: VariantFormat::NewType(Box::new(value)),
+                };
+                (pos as u32, variant)
+            })
+            .collect();
+        reg.insert((None, enum_name.clone()), ContainerFormat::Enum(variants));
+        config.add_comment(
+            &[&enum_name],
+            &format!(
+                "Decoded `{}` of a `{}` mode item, keyed by type.",
+                field, mode
+            ),
+        );
+
+        let arms = members
+            .iter()
+            .map(|member| {
+                format!(
+                    "            ({:?}, {:?}) => Some({}.map({}::{})),\n",
+                    mode, member, decode, enum_name, member
+                )
+            })
+            .collect::<String>();
+        code.push(format!(
+            r#"    /// Decodes `{field}` into the generated definition of the item type, or returns `None`
+    /// if the item is not a `{mode}` mode item of a known type.
+    pub fn {method}(&self) -> Option<Result<{enum_name}, tfschema_bindgen::runtime::DecodeError>> {{
+        match (self.mode.as_str(), self.r#type.as_str()) {{
+{arms}            _ => None,
+        }}
+    }}"#,
+            field = field,
+            mode = mode,
+            method = method,
+            enum_name = enum_name,
+            arms = arms
+        ));
+    }
+    config.add_custom_code(
+        &[container],
+        &format!("impl {} {{\n{}\n}}", container, code.join("\n\n")),
+    );
+}
+
+/// Generate the model of plan documents printed by `terraform show -json`, along with the
+/// `resource_change_values` and `data_change_values` enums which changes of known resources
+/// and data sources are decoded into.
+fn generate_plan(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) {
+    let dynamic = || type_name("Dynamic");
+    reg.insert(
+        (None, "plan".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("format_version", Format::Str),
+            field("terraform_version", optional(Format::Str)),
+            field("planned_values", optional(type_name("state_values"))),
+            field(
+                "resource_changes",
+                Format::Seq(Box::new(type_name("plan_resource_change"))),
+            ),
+            field(
+                "output_changes",
+                Format::Map {
+                    key: Box::new(Format::Str),
+                    value: Box::new(type_name("plan_change")),
+                },
+            ),
+            field("prior_state", optional(type_name("state"))),
+        ]),
+    );
+    config.add_comment(
+        &["plan"],
+        "Plan document, as printed by `terraform show -json`.",
+    );
+    reg.insert(
+        (None, "plan_resource_change".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("address", Format::Str),
+            field("module_address", optional(Format::Str)),
+            field("mode", Format::Str),
+            field("type", Format::Str),
+            field("name", Format::Str),
+            field("index", optional(dynamic())),
+            field("provider_name", Format::Str),
+            field("deposed", optional(Format::Str)),
+            field("change", type_name("plan_change")),
+            field("action_reason", optional(Format::Str)),
+        ]),
+    );
+    config.add_comment(
+        &["plan_resource_change", "change"],
+        "Planned change, see `decode_resource` and `decode_data`.",
+    );
+    reg.insert(
+        (None, "plan_change".to_owned()),
+        ContainerFormat::Struct(vec![
+            field("actions", Format::Seq(Box::new(Format::Str))),
+            field("before", optional(dynamic())),
+            field("after", optional(dynamic())),
+            field("after_unknown", optional(dynamic())),
+            field("before_sensitive", optional(dynamic())),
+            field("after_sensitive", optional(dynamic())),
+        ]),
+    );
+    config.add_comment(
+        &["plan_change", "after"],
+        "Values after the change, without those only known after apply.",
+    );
+    config.add_comment(
+        &["plan_change", "after_unknown"],
+        "Mask of `after`, where values only known after apply are replaced by `true`.",
+    );
+    config.add_comment(
+        &["plan_change", "before_sensitive"],
+        "Mask of `before`, where sensitive values are replaced by `true`.",
+    );
+    config.add_comment(
+        &["plan_change", "after_sensitive"],
+        "Mask of `after`, where sensitive values are replaced by `true`.",
+    );
+    config.add_custom_code(
+        &["plan_change"],
+        r#"impl plan_change {
+    /// Decodes `before` and `after` into the given generated definition. Values only known
+    /// after apply are left out of `after` (see `after_unknown`), so they decode as `None`
+    /// into the `_state` structs, where every attribute is optional.
+    pub fn decode<T: serde::de::DeserializeOwned>(
+        &self,
+    ) -> Result<Change<T>, tfschema_bindgen::runtime::DecodeError> {
+        Ok(Change {
+            before: self.before.as_ref().map(Dynamic::decode).transpose()?,
+            after: self.after.as_ref().map(Dynamic::decode).transpose()?,
+        })
+    }
+}"#,
+    );
+
+    export_decoders(
+        roots,
+        reg,
+        config,
+        ("plan_resource_change", "change"),
+        ("change_values", plan_struct_name),
+        "self.change.decode()",
+        |t| format!("Change<{}>", t),
+    );
+}
+
+fn export_roots(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) {
     for (root_name, root_members) in roots {
         let mut enumz = BTreeMap::new();
         for (pos, member) in root_members.iter().enumerate() {
-            let mut variant_type_name = format!("Vec<Map<String, Vec<{}_details>>>", member);
+            let mut variant_type_name = format!(
+                "Vec<Map<String, Vec<{}>>>",
+                input_struct_name(member, config)
+            );
+            let mut function = "deserialize_labeled_blocks";
 
             if root_name.to_string().eq("provider") {
                 variant_type_name = format!("Vec<{}_details>", member);
+                function = "deserialize_blocks";
             }
+            let root = format!("{}_root", root_name);
+            config.add_annotation(&[&root, member], deserialize_with(function, false));
 
             enumz.insert(
                 pos as u32,
@@ -207,51 +770,114 @@ fn export_roots(roots: &BTreeMap<&str, Vec<&str>>, reg: &mut Registry) {
     }
 }
 
+/// Export roots as structs with a field per type, holding providers (which may be declared
+/// more than once, under different aliases) or items keyed by their name.
+fn export_object_roots(
+    roots: &BTreeMap<&str, Vec<&str>>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) {
+    for (root_name, root_members) in roots {
+        let root = format!("{}_root", root_name);
+        let mut fields = Vec::new();
+        for member in root_members {
+            let (format, function) = if root_name.to_string().eq("provider") {
+                let format = Format::Seq(Box::new(type_name(&format!("{}_details", member))));
+                (format, "deserialize_blocks")
+            } else {
+                let format = Format::Map {
+                    key: Box::new(Format::Str),
+                    value: Box::new(type_name(&input_struct_name(member, config))),
+                };
+                (format, "deserialize_labels")
+            };
+            config.add_annotation(&[&root, member], deserialize_with(function, true));
+            fields.push(field(member, optional(format)));
+        }
+        reg.insert((None, root.clone()), ContainerFormat::Struct(fields));
+        config.add_custom_code(
+            &[&root],
+            &format!(
+                r#"impl {} {{
+    /// Whether no item is declared.
+    pub fn is_empty(&self) -> bool {{
+        *self == Self::default()
+    }}
+}}"#,
+                root
+            ),
+        );
+    }
+}
+
+/// Attribute deserializing a field (or variant) with a function of the runtime module.
+fn deserialize_with(function: &str, default: bool) -> String {
+    format!(
+        "#[serde({}deserialize_with = \"tfschema_bindgen::runtime::{}\")]",
+        if default { "default, " } else { "" },
+        function
+    )
+}
+
+/// Attribute letting nested blocks be deserialized from both JSON shapes (single object or
+/// array of objects, per label for blocks keyed by label).
+fn block_field_annotation(f: &Format) -> Option<String> {
+    match f {
+        Format::Seq(_) => Some(deserialize_with("deserialize_blocks", false)),
+        Format::TypeName(t) if t.starts_with("Set<") => {
+            Some(deserialize_with("deserialize_blocks", false))
+        }
+        Format::Map { .. } => Some(deserialize_with("deserialize_labels", false)),
+        Format::Option(_) => Some(deserialize_with("deserialize_block", true)),
+        Format::TypeName(_) => Some(deserialize_with("deserialize_block", false)),
+        _ => None,
+    }
+}
+
 fn export_attributes(
+    namespace: Option<&String>,
+    parent_name: &str,
     attrs: &BTreeMap<String, Attribute>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
 ) -> std::result::Result<Option<ContainerFormat>, Box<dyn std::error::Error>> {
     let mut target_attrs = Vec::new();
     for (an, at) in attrs {
-        let an = RESERVED_WORDS
-            .iter()
-            .find(|w| an == &w.to_string())
-            .map(|w| format!("r#{}", w))
-            .unwrap_or_else(|| an.to_string());
-
-        let f = match &at.r#type {
-            AttributeType(Value::String(t)) if t == "string" => Format::Str,
-            AttributeType(Value::String(t)) if t == "bool" => Format::Bool,
-            AttributeType(Value::String(t)) if t == "number" => Format::I64,
-            AttributeType(Value::String(t)) if t == "set" || t == "list" => {
-                Format::Seq(Box::new(Format::Str))
-            }
-            AttributeType(Value::String(t)) if t == "map" => Format::Map {
-                key: Box::new(Format::Str),
-                value: Box::new(Format::Str),
-            },
-            AttributeType(Value::String(t)) => {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Unknown type {}", t),
-                )))
-            }
-            AttributeType(Value::Array(t))
-                if t.first().unwrap() == "set" || t.first().unwrap() == "list" =>
-            {
-                Format::Seq(Box::new(Format::Str))
-            }
-            /* TODO: It will assume a map of strings even if the specified type is of a different kind (e.g. map of object) */
-            AttributeType(Value::Array(t)) if t.first().unwrap() == "map" => Format::Map {
-                key: Box::new(Format::Str),
-                value: Box::new(Format::Str),
-            },
-            unknown => {
-                return Err(Box::new(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("Type {:?} not supported", unknown),
-                )))
+        let object_type_ns = namespace.map_or_else(
+            || format!("{}_object_type", parent_name),
+            |v| format!("{}_{}_object_type", parent_name, v),
+        );
+        let f = match &at.nested_type {
+            Some(nt) => {
+                let nested_type_fqn = format!("{}_{}", object_type_ns, an);
+                let cf = export_attributes(None, &nested_type_fqn, &nt.attributes, reg, config)?;
+                export_attribute_docs(&nested_type_fqn, &nt.attributes, config);
+                reg.insert(
+                    (Some(object_type_ns), an.to_owned()),
+                    cf.unwrap_or(ContainerFormat::Struct(Vec::new())),
+                );
+                // optionality follows the attribute flags below, and unlike blocks, values
+                // of list or set attributes are always written as arrays
+                export_nesting_mode(nt.nesting_mode.as_ref(), true, None, &nested_type_fqn)
             }
+            None => export_type(
+                &at.r#type.0,
+                &(Some(object_type_ns), an.to_owned()),
+                reg,
+                config,
+            )?,
+        };
+        let f = if at.literal {
+            f
+        } else {
+            export_expression(f, config)
+        };
+        let f = if at.sensitive == Some(true) {
+            export_sensitive(f, config)
+        } else {
+            f
         };
+
         let attr_fmt = match (at.optional, at.computed) {
             (Some(opt), _) if opt => Format::Option(Box::new(f.clone())),
             (_, Some(cmp)) if cmp => Format::Option(Box::new(f.clone())),
@@ -259,7 +885,7 @@ fn export_attributes(
         };
 
         target_attrs.push(Named {
-            name: an,
+            name: field_name(an),
             value: attr_fmt,
         });
     }
@@ -270,176 +896,2289 @@ fn export_attributes(
     }
 }
 
-fn inject_meta_arguments(blk: &mut Block) {
-    let depends_on_attr = Attribute {
-        r#type: AttributeType(serde_json::json!(["set"])),
-        optional: Some(true),
-        ..Default::default()
-    };
-    let count_attr = Attribute {
-        r#type: AttributeType(serde_json::json!("number")),
-        optional: Some(true),
-        ..Default::default()
-    };
+/// Wrap the format of an attribute in `Expr` as requested by `config`, along with elements of
+/// collections (strings included) so they may hold expressions too. Strings and dynamic values
+/// may already hold expressions.
+fn export_expression(f: Format, config: &CodeGeneratorConfig) -> Format {
+    match &f {
+        _ if !config.expression_wrapper => f,
+        Format::Str => f,
+        _ => export_expression_element(f),
+    }
+}
 
-    let for_each_attr = Attribute {
-        r#type: AttributeType(serde_json::json!(["set"])),
-        optional: Some(true),
-        ..Default::default()
+fn export_expression_element(f: Format) -> Format {
+    let f = match f {
+        Format::TypeName(name) if name == "Dynamic" => return Format::TypeName(name),
+        Format::Seq(e) => Format::Seq(Box::new(export_expression_element(*e))),
+        Format::Map { key, value } => Format::Map {
+            key,
+            value: Box::new(export_expression_element(*value)),
+        },
+        Format::Tuple(elems) => {
+            Format::Tuple(elems.into_iter().map(export_expression_element).collect())
+        }
+        f => f,
     };
+    Format::TypeName(format!("Expr<{}>", quote_type(&f)))
+}
 
-    let provider_attr = Attribute {
-        r#type: AttributeType(serde_json::json!("string")),
-        optional: Some(true),
-        ..Default::default()
+/// Wrap the format of a sensitive attribute as requested by `config`.
+fn export_sensitive(f: Format, config: &CodeGeneratorConfig) -> Format {
+    if !config.sensitive_wrapper {
+        return f;
+    }
+    let wrapper = if config.zeroize_sensitive && is_zeroizable(&f) {
+        "Secret"
+    } else {
+        "Sensitive"
     };
+    Format::TypeName(format!("{}<{}>", wrapper, quote_type(&f)))
+}
 
-    if let Some(attrs) = blk.attributes.as_mut() {
-        attrs.insert("depends_on".to_owned(), depends_on_attr);
-        attrs.insert("count".to_owned(), count_attr);
-        attrs.insert("for_each".to_owned(), for_each_attr);
-        attrs.insert("provider".to_owned(), provider_attr);
+/// Whether values of the format implement `Zeroize`.
+fn is_zeroizable(f: &Format) -> bool {
+    match f {
+        Format::Str | Format::Bool | Format::I64 => true,
+        Format::Seq(e) | Format::Option(e) => is_zeroizable(e),
+        _ => false,
     }
 }
 
-fn export_block(
-    namespace: Option<String>,
-    name: &str,
-    blk: Block,
-    reg: &mut Registry,
-) -> std::result::Result<(), Box<dyn std::error::Error>> {
-    let mut cf1 = export_attributes(blk.attributes.as_ref().unwrap())?;
-    if let Some(bt) = &blk.block_types {
-        for (block_type_name, nested_block) in bt {
-            export_block_type(
-                namespace.as_ref(),
-                name,
-                block_type_name,
-                nested_block,
-                reg,
-                cf1.as_mut().unwrap(),
-            )?;
+fn export_attribute_docs(
+    struct_name: &str,
+    attrs: &BTreeMap<String, Attribute>,
+    config: &mut CodeGeneratorConfig,
+) {
+    for (an, at) in attrs {
+        if let Some(desc) = &at.description {
+            let doc = doc_comment(desc, at.description_kind.as_ref());
+            config.add_comment(&[struct_name, &field_name(an)], &doc);
+        }
+        if at.deprecated == Some(true) {
+            let annotation = deprecated_annotation(at.description.as_ref());
+            config.add_annotation(&[struct_name, &field_name(an)], annotation);
         }
     }
+}
 
-    reg.insert((None, format!("{}_details", name)), cf1.unwrap());
+/// Turn a schema description into documentation rendered by rustdoc as intended.
+fn doc_comment(desc: &str, kind: Option<&StringKind>) -> String {
+    match kind {
+        Some(StringKind::Markdown) => markdown_to_rustdoc(desc),
+        _ => desc
+            .lines()
+            .map(|l| escape_markdown(l.trim_start()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    }
+}
 
-    Ok(())
+/// Escape characters that would make rustdoc read plain text as markup.
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for (pos, c) in text.chars().enumerate() {
+        if matches!(c, '\\' | '`' | '*' | '[' | ']' | '<' | '>') || (pos == 0 && c == '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
 }
 
-fn export_block_type(
-    namespace: Option<&String>,
-    parent_name: &str,
-    name: &str,
-    blk: &NestedBlock,
-    reg: &mut Registry,
-    cf: &mut ContainerFormat,
-) -> std::result::Result<(), Box<dyn std::error::Error>> {
-    let mut inner_block_types = Vec::new();
-    if let Some(attrs) = &blk.block.attributes {
-        let mut nested_cf = export_attributes(attrs)?;
-        let block_type_ns = namespace.map_or_else(
-            || format!("{}_block_type", parent_name),
-            |v| format!("{}_{}_block_type", parent_name, v),
-        );
-        let block_type_fqn = namespace.map_or_else(
-            || format!("{}_block_type_{}", parent_name, name.to_owned()),
-            |v| format!("{}_{}_block_type_{}", parent_name, v, name.to_owned()),
-        );
-
-        // export inner block types
-        if let Some(bt) = &blk.block.block_types {
-            for (block_type_name, nested_block) in bt {
-                export_block_type(
-                    namespace,
-                    name,
-                    block_type_name,
-                    nested_block,
-                    reg,
-                    nested_cf.as_mut().unwrap(),
-                )?;
+/// Normalize markdown descriptions: code blocks are marked as HCL so that they are not
+/// run as doctests, links relative to the Terraform website are made absolute and bare
+/// brackets are escaped so that they are not taken for intra-doc links.
+fn markdown_to_rustdoc(desc: &str) -> String {
+    let mut lines = Vec::new();
+    let mut in_fence = false;
+    let mut in_indented = false;
+    let mut prev_blank = true;
+    for line in desc.lines() {
+        let trimmed = line.trim_start();
+        if in_indented
+            && !trimmed.is_empty()
+            && !line.starts_with("    ")
+            && !line.starts_with('\t')
+        {
+            lines.push("```".to_owned());
+            in_indented = false;
+        }
+        if trimmed.starts_with("```") {
+            if !in_fence && trimmed.trim_end() == "```" {
+                lines.push("```hcl".to_owned());
+            } else {
+                lines.push(trimmed.to_owned());
+            }
+            in_fence = !in_fence;
+        } else if in_fence {
+            lines.push(line.to_owned());
+        } else if in_indented
+            || (prev_blank
+                && !trimmed.is_empty()
+                && (line.starts_with("    ") || line.starts_with('\t')))
+        {
+            if !in_indented {
+                lines.push("```hcl".to_owned());
+                in_indented = true;
             }
+            let dedented = line
+                .strip_prefix("    ")
+                .or_else(|| line.strip_prefix('\t'));
+            lines.push(dedented.unwrap_or(trimmed).to_owned());
+        } else {
+            // leave inline code spans untouched
+            let text = line
+                .split('`')
+                .enumerate()
+                .map(|(pos, span)| {
+                    if pos % 2 == 0 {
+                        fix_markdown_text(span)
+                    } else {
+                        span.to_owned()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("`");
+            lines.push(text);
         }
-        reg.insert((Some(block_type_ns), name.to_owned()), nested_cf.unwrap());
-        inner_block_types.push((name, block_type_fqn));
+        prev_blank = trimmed.is_empty();
     }
+    if in_fence || in_indented {
+        lines.push("```".to_owned());
+    }
+    lines.join("\n")
+}
 
-    if let ContainerFormat::Struct(ref mut attrs) = cf {
-        for (_, (n, fqn)) in inner_block_types.iter().enumerate() {
-            attrs.push(Named {
-                name: n.to_string(),
-                value: Format::Option(Box::new(Format::Seq(Box::new(Format::TypeName(
-                    fqn.to_string(),
-                ))))),
-            });
+/// Make relative links absolute and escape brackets that are not part of a link.
+fn fix_markdown_text(text: &str) -> String {
+    let mut fixed = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('[') {
+        fixed.push_str(&rest[..start]);
+        let tail = &rest[start..];
+        match tail.find(']') {
+            Some(end) => {
+                let after = &tail[end + 1..];
+                if after.starts_with("(/") {
+                    fixed.push_str(&tail[..=end]);
+                    fixed.push('(');
+                    fixed.push_str(TERRAFORM_WEBSITE_URL);
+                    rest = &after[1..];
+                } else if after.starts_with('(')
+                    || after.starts_with('[')
+                    || after.starts_with(':')
+                    || fixed.ends_with(']')
+                {
+                    fixed.push_str(&tail[..=end]);
+                    rest = after;
+                } else {
+                    fixed.push_str("\\[");
+                    fixed.push_str(&tail[1..end]);
+                    fixed.push_str("\\]");
+                    rest = after;
+                }
+            }
+            None => {
+                fixed.push_str("\\[");
+                rest = &tail[1..];
+            }
         }
-    };
+    }
+    fixed.push_str(rest);
+    fixed
+}
 
-    Ok(())
+/// `#[deprecated]` attribute noting the description of the deprecated item, if any.
+fn deprecated_annotation(desc: Option<&String>) -> String {
+    match desc.map(|d| d.trim()).filter(|d| !d.is_empty()) {
+        Some(note) => format!("#[deprecated(note = {:?})]", note),
+        None => "#[deprecated]".to_owned(),
+    }
 }
 
-pub fn read_tf_schema_from_file<P: AsRef<Path>>(
-    path: P,
-) -> std::result::Result<TerraformSchemaExport, Box<dyn std::error::Error>> {
-    // Open the file in read-only mode with buffer.
-    let file = File::open(path).expect("input file must be readable");
-    let reader = BufReader::new(file);
-    // Read the JSON contents of the file as an instance of `User`.
-    let d: TerraformSchemaExport = serde_json::from_reader(reader)?;
+/// Field name of the given attribute, escaping reserved words.
+fn field_name(an: &str) -> String {
+    RESERVED_WORDS
+        .iter()
+        .find(|w| an == **w)
+        .map(|w| format!("r#{}", w))
+        .unwrap_or_else(|| an.to_string())
+}
 
-    // Return the `Diagram`.
-    Ok(d)
+fn export_type(
+    t: &Value,
+    type_name: &QualifiedName,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<Format, Box<dyn std::error::Error>> {
+    let f = match t {
+        Value::String(t) if t == "string" => Format::Str,
+        Value::String(t) if t == "bool" => Format::Bool,
+        Value::String(t) if t == "number" => Format::I64,
+        Value::String(t) if t == "dynamic" => Format::TypeName("Dynamic".to_owned()),
+        Value::String(t) if t == "set" || t == "list" => Format::Seq(Box::new(Format::Str)),
+        Value::String(t) if t == "map" => Format::Map {
+            key: Box::new(Format::Str),
+            value: Box::new(Format::Str),
+        },
+        Value::String(t) => {
+            return Err(Box::new(std::io::Error::other(format!(
+                "Unknown type {}",
+                t
+            ))))
+        }
+        Value::Array(t) if t.first().and_then(Value::as_str) == Some("object") => {
+            export_object_type(t, type_name, reg, config)?
+        }
+        Value::Array(t) if t.first().and_then(Value::as_str) == Some("tuple") => {
+            let (ns, name) = type_name;
+            let mut elems = Vec::new();
+            if let Some(Value::Array(ets)) = t.get(1) {
+                for (pos, et) in ets.iter().enumerate() {
+                    let elem_name = (ns.clone(), format!("{}_{}", name, pos));
+                    elems.push(export_type(et, &elem_name, reg, config)?);
+                }
+            }
+            Format::Tuple(elems)
+        }
+        Value::Array(t) => {
+            // element types default to string when omitted (e.g. meta-arguments)
+            let elem = match t.get(1) {
+                Some(et) => export_type(et, type_name, reg, config)?,
+                None => Format::Str,
+            };
+            match t.first().and_then(Value::as_str) {
+                Some("set") | Some("list") => Format::Seq(Box::new(elem)),
+                Some("map") => Format::Map {
+                    key: Box::new(Format::Str),
+                    value: Box::new(elem),
+                },
+                _ => {
+                    return Err(Box::new(std::io::Error::other(format!(
+                        "Type {:?} not supported",
+                        t
+                    ))))
+                }
+            }
+        }
+        unknown => {
+            return Err(Box::new(std::io::Error::other(format!(
+                "Type {:?} not supported",
+                unknown
+            ))))
+        }
+    };
+    Ok(f)
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
-    use crate::test_utils::{config, datasource_root, provider_root, resource_root};
-    use std::fs::File;
-    use std::process::Command;
-    use tempfile::tempdir;
+fn export_object_type(
+    t: &[Value],
+    type_name: &QualifiedName,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<Format, Box<dyn std::error::Error>> {
+    let (ns, name) = type_name;
+    let object_type_fqn = ns
+        .as_ref()
+        .map_or_else(|| name.to_owned(), |v| format!("{}_{}", v, name));
 
-    #[test]
-    fn test_deserialize_example_tf_schema() {
-        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+    // object attributes listed in the optional third element may be omitted
+    let optional_attrs: Vec<&str> = t
+        .get(2)
+        .and_then(Value::as_array)
+        .map(|o| o.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let mut attrs = BTreeMap::new();
+    if let Some(Value::Object(ots)) = t.get(1) {
+        for (an, at) in ots {
+            let optional = optional_attrs.contains(&an.as_str());
+            attrs.insert(
+                an.to_owned(),
+                Attribute {
+                    r#type: AttributeType(at.clone()),
+                    required: Some(!optional),
+                    optional: Some(optional),
+                    ..Default::default()
+                },
+            );
+        }
+    }
 
-        assert!(tf_schema.is_ok());
-        let test_schema = tf_schema
-            .as_ref()
-            .unwrap()
-            .provider_schemas
+    let cf = export_attributes(None, &object_type_fqn, &attrs, reg, config)?;
+    reg.insert(
+        type_name.clone(),
+        cf.unwrap_or(ContainerFormat::Struct(Vec::new())),
+    );
+
+    Ok(Format::TypeName(object_type_fqn))
+}
+
+/// Add the meta-arguments of managed resources, or of data sources if `managed` is false.
+/// Those typed after runtime definitions are added by `inject_meta_fields` once exported.
+fn inject_meta_arguments(blk: &mut Block, managed: bool) {
+    let depends_on_attr = Attribute {
+        r#type: AttributeType(serde_json::json!(["set"])),
+        description: Some("Hidden dependencies that Terraform cannot infer.".to_owned()),
+        optional: Some(true),
+        literal: true,
+        ..Default::default()
+    };
+    let attrs = blk.attributes.get_or_insert_with(BTreeMap::new);
+    attrs.insert("depends_on".to_owned(), depends_on_attr);
+    blk.block_types
+        .get_or_insert_with(BTreeMap::new)
+        .insert("lifecycle".to_owned(), lifecycle_block(managed));
+}
+
+/// Lifecycle block of managed resources, or of data sources (which only accept conditions)
+/// if `managed` is false.
+fn lifecycle_block(managed: bool) -> NestedBlock {
+    let flag = || serde_json::json!("bool");
+    let references = || serde_json::json!(["list", "string"]);
+    let mut block = literal_block(
+        "Customizes the lifecycle of the object.",
+        vec![
+            (
+                "create_before_destroy",
+                argument(
+                    flag(),
+                    "Create the replacement object before destroying the current one.",
+                ),
+            ),
+            (
+                "prevent_destroy",
+                argument(flag(), "Reject any plan that would destroy the object."),
+            ),
+            (
+                "ignore_changes",
+                argument(
+                    references(),
+                    "Attributes whose changes are ignored when planning updates.",
+                ),
+            ),
+            (
+                "replace_triggered_by",
+                argument(
+                    references(),
+                    "References whose changes cause the object to be replaced.",
+                ),
+            ),
+        ],
+    );
+    if !managed {
+        block.attributes = Some(BTreeMap::new());
+    }
+
+    let mut block_types = BTreeMap::new();
+    block_types.insert(
+        "precondition".to_owned(),
+        condition_block("Condition checked before evaluating the object."),
+    );
+    block_types.insert(
+        "postcondition".to_owned(),
+        condition_block("Condition checked after evaluating the object."),
+    );
+    block.block_types = Some(block_types);
+
+    NestedBlock {
+        block,
+        nesting_mode: Some("single".to_owned()),
+        ..Default::default()
+    }
+}
+
+fn condition_block(description: &str) -> NestedBlock {
+    let expression = || serde_json::json!("string");
+    NestedBlock {
+        block: injected_block(
+            description,
+            vec![
+                (
+                    "condition",
+                    required_argument(expression(), "Expression which must be true."),
+                ),
+                (
+                    "error_message",
+                    required_argument(expression(), "Error reported when the condition is false."),
+                ),
+            ],
+        ),
+        nesting_mode: Some("list".to_owned()),
+        ..Default::default()
+    }
+}
+
+/// Remove the attributes users cannot set (computed but not optional) from a block.
+fn remove_computed_attributes(blk: &mut Block) {
+    if let Some(attrs) = blk.attributes.as_mut() {
+        retain_arguments(attrs);
+    }
+    for nested_block in blk.block_types.iter_mut().flat_map(|bt| bt.values_mut()) {
+        remove_computed_attributes(&mut nested_block.block);
+    }
+}
+
+fn retain_arguments(attrs: &mut BTreeMap<String, Attribute>) {
+    attrs.retain(|_, at| {
+        at.computed != Some(true) || at.optional == Some(true) || at.required == Some(true)
+    });
+    for nt in attrs.values_mut().filter_map(|at| at.nested_type.as_mut()) {
+        retain_arguments(&mut nt.attributes);
+    }
+}
+
+/// Export a resource or data source as a `{name}_details` struct, or as a `{name}_config`
+/// struct when `config` asks for separate state, along with a `{name}_state` struct. The
+/// details struct is made of `input`, the config struct of the arguments of `input`, the state
+/// struct of every attribute of `state` (all of them optional, as in plans).
+fn export_schema_item(
+    namespace: &str,
+    name: &str,
+    input: Block,
+    state: &Block,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let namespace = Some(namespace.to_owned());
+    if config.separate_state {
+        let mut input = input;
+        remove_computed_attributes(&mut input);
+        let config_name = format!("{}_config", name);
+        export_block(
+            namespace.clone(),
+            &config_name,
+            &config_name,
+            input,
+            reg,
+            config,
+        )?;
+    } else {
+        let struct_name = format!("{}_details", name);
+        export_block(namespace.clone(), name, &struct_name, input, reg, config)?;
+    }
+
+    let mut state = state.clone();
+    make_attributes_optional(&mut state);
+    let state_name = format!("{}_state", name);
+    export_block(namespace, &state_name, &state_name, state, reg, config)
+}
+
+/// Make every attribute (and nested block) of a block optional, as values only known after
+/// apply are left out of plans.
+fn make_attributes_optional(blk: &mut Block) {
+    for at in blk
+        .attributes
+        .iter_mut()
+        .flat_map(|attrs| attrs.values_mut())
+    {
+        make_optional(at);
+    }
+    for nested_block in blk.block_types.iter_mut().flat_map(|bt| bt.values_mut()) {
+        nested_block.min_items = None;
+        make_attributes_optional(&mut nested_block.block);
+    }
+}
+
+fn make_optional(at: &mut Attribute) {
+    at.optional = Some(true);
+    for nt in at.nested_type.iter_mut() {
+        nt.attributes.values_mut().for_each(make_optional);
+    }
+}
+
+/// Generate a `{name}` module (`data_{name}` for data sources) after the state struct of a
+/// resource or data source, with a `reference` function building references to attributes of
+/// the item of that name (e.g. `aws_instance::reference("web").attr_id()`).
+fn export_references(
+    mode: &str,
+    name: &str,
+    state: &Block,
+    reg: &Registry,
+    config: &mut CodeGeneratorConfig,
+) {
+    let struct_name = state_struct_name(name, config);
+    let (module, address) = match mode {
+        "data" => (format!("data_{}", name), format!("data.{}", name)),
+        _ => (name.to_owned(), name.to_owned()),
+    };
+    let fields = match reg.get(&(None, struct_name.clone())) {
+        Some(ContainerFormat::Struct(fields)) => fields,
+        _ => return,
+    };
+    let accessors = state
+        .attributes
+        .iter()
+        .flatten()
+        .filter_map(|(an, _)| {
+            let f = fields.iter().find(|f| f.name == field_name(an))?;
+            Some(format!(
+                r#"        /// Reference to the `{an}` attribute.
+        pub fn attr_{an}(&self) -> tfschema_bindgen::runtime::Expr<{ty}> {{
+            tfschema_bindgen::runtime::Expr::interpolate(&format!("{{}}.{an}", self.0))
+        }}
+"#,
+                an = an,
+                ty = reference_type(&f.value)
+            ))
+        })
+        .collect::<Vec<_>>();
+    config.add_custom_code(
+        &[&struct_name],
+        &format!(
+            r#"/// References to attributes of `{address}` items, for use in arguments of other items.
+pub mod {module} {{
+    use super::*;
+
+    /// Reference to the `{address}` item named `name`.
+    pub fn reference(name: &str) -> Reference {{
+        Reference(format!("{address}.{{}}", name))
+    }}
+
+    /// Address of a `{address}` item, see `reference`.
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct Reference(String);
+
+    impl Reference {{
+{accessors}    }}
+}}"#,
+            address = address,
+            module = module,
+            accessors = accessors.join("\n")
+        ),
+    );
+}
+
+/// Implement `runtime::Resource` for the structs generated for a provider, resource or data
+/// source, given as `(kind, type name, provider name, schema version)`.
+fn export_resource_impl(
+    (kind, name, provider, version): (&str, &str, &str, i64),
+    blk: &Block,
+    struct_names: &[String],
+    config: &mut CodeGeneratorConfig,
+) {
+    let flag = |f: Option<bool>| f.unwrap_or(false);
+    let attributes = blk
+        .attributes
+        .iter()
+        .flatten()
+        .map(|(an, at)| {
+            format!(
+                r#"        tfschema_bindgen::runtime::AttributeSchema {{
+            name: {:?},
+            required: {},
+            optional: {},
+            computed: {},
+            sensitive: {},
+            deprecated: {},
+        }},
+"#,
+                an,
+                flag(at.required),
+                flag(at.optional),
+                flag(at.computed),
+                flag(at.sensitive),
+                flag(at.deprecated)
+            )
+        })
+        .collect::<String>();
+    let mut struct_names = struct_names.to_vec();
+    struct_names.dedup();
+    for struct_name in &struct_names {
+        config.add_custom_code(
+            &[struct_name],
+            &format!(
+                r#"impl tfschema_bindgen::runtime::Resource for {struct_name} {{
+    const TYPE_NAME: &'static str = {name:?};
+    const KIND: tfschema_bindgen::runtime::ResourceKind = tfschema_bindgen::runtime::ResourceKind::{kind};
+    const PROVIDER: &'static str = {provider:?};
+    const SCHEMA_VERSION: i64 = {version};
+    const ATTRIBUTES: &'static [tfschema_bindgen::runtime::AttributeSchema] = &[
+{attributes}    ];
+}}"#,
+                struct_name = struct_name,
+                name = name,
+                kind = kind,
+                provider = provider,
+                version = version,
+                attributes = attributes
+            ),
+        );
+    }
+}
+
+/// Type of the value an attribute refers to, leaving out optionality and wrappers.
+fn reference_type(f: &Format) -> String {
+    let f = match f {
+        Format::Option(f) => f,
+        f => f,
+    };
+    let mut ty = quote_type(f);
+    for wrapper in &["Sensitive<", "Secret<", "Expr<"] {
+        if ty.starts_with(wrapper) {
+            ty = ty[wrapper.len()..ty.len() - 1].to_owned();
+        }
+    }
+    ty
+}
+
+/// Optional argument of a block injected into the schema.
+fn argument(t: Value, description: &str) -> Attribute {
+    Attribute {
+        r#type: AttributeType(t),
+        description: Some(description.to_owned()),
+        description_kind: Some(StringKind::Markdown),
+        optional: Some(true),
+        ..Default::default()
+    }
+}
+
+/// Required argument of a block injected into the schema.
+fn required_argument(t: Value, description: &str) -> Attribute {
+    Attribute {
+        optional: None,
+        required: Some(true),
+        ..argument(t, description)
+    }
+}
+
+/// Sensitive argument of a block injected into the schema.
+fn sensitive_argument(t: Value, description: &str) -> Attribute {
+    Attribute {
+        sensitive: Some(true),
+        ..argument(t, description)
+    }
+}
+
+fn injected_block(description: &str, attrs: Vec<(&str, Attribute)>) -> Block {
+    Block {
+        attributes: Some(
+            attrs
+                .into_iter()
+                .map(|(name, attr)| (name.to_owned(), attr))
+                .collect(),
+        ),
+        description: Some(description.to_owned()),
+        description_kind: Some(StringKind::Markdown),
+        ..Default::default()
+    }
+}
+
+/// Injected block whose arguments must be literals (meta-arguments and settings), where
+/// Terraform rejects expressions.
+fn literal_block(description: &str, mut attrs: Vec<(&str, Attribute)>) -> Block {
+    for (_, attr) in attrs.iter_mut() {
+        attr.literal = true;
+    }
+    injected_block(description, attrs)
+}
+
+/// Add the meta-arguments typed after runtime definitions to the input struct of a resource
+/// or data source.
+fn inject_meta_fields(struct_name: &str, reg: &mut Registry, config: &mut CodeGeneratorConfig) {
+    let meta_fields = [
+        ("count", "Count", "Number of instances to create."),
+        (
+            "for_each",
+            "ForEach",
+            "Instances to create, one per element of a set or a map.",
+        ),
+        (
+            "provider",
+            "ProviderRef",
+            "Provider configuration to use instead of the default one (e.g. `aws.west`).",
+        ),
+    ];
+    for (name, runtime_type, description) in meta_fields.iter() {
+        config.add_comment(&[struct_name, name], description);
+        if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, struct_name.to_owned()))
+        {
+            fields.push(field(name, optional(type_name(runtime_type))));
+        }
+    }
+
+    // `ignore_changes` also accepts the `all` keyword
+    let lifecycle = match reg.get(&(None, struct_name.to_owned())) {
+        Some(ContainerFormat::Struct(fields)) => fields.iter().find_map(|f| match &f.value {
+            Format::Option(t) if f.name == "lifecycle" => match t.as_ref() {
+                Format::TypeName(fqn) => fqn.strip_suffix("_lifecycle").map(String::from),
+                _ => None,
+            },
+            _ => None,
+        }),
+        _ => None,
+    };
+    if let Some(ContainerFormat::Struct(fields)) =
+        lifecycle.and_then(|ns| reg.get_mut(&(Some(ns), "lifecycle".to_owned())))
+    {
+        for f in fields.iter_mut().filter(|f| f.name == "ignore_changes") {
+            f.value = optional(type_name("IgnoreChanges"));
+        }
+    }
+}
+
+/// Add the `alias` meta-argument to a provider block.
+fn inject_provider_alias(blk: &mut Block) {
+    let alias_attr = argument(
+        serde_json::json!("string"),
+        "Name of an additional configuration of the provider, referenced as `{provider}.{alias}`.",
+    );
+    blk.attributes
+        .get_or_insert_with(BTreeMap::new)
+        .insert("alias".to_owned(), alias_attr);
+}
+
+/// Add the `connection` block and `provisioner` blocks to the input struct of a resource.
+fn inject_provisioners(struct_name: &str, reg: &mut Registry, config: &mut CodeGeneratorConfig) {
+    let connection = connection_field(struct_name, config);
+    let provisioner = field(
+        "provisioner",
+        Format::Seq(Box::new(type_name("provisioner"))),
+    );
+    config.add_comment(
+        &[struct_name, "provisioner"],
+        "Actions run, in order, when the resource is created (or destroyed).",
+    );
+    config.add_annotation(
+        &[struct_name, "provisioner"],
+        deserialize_with("deserialize_tagged_blocks", false),
+    );
+    if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, struct_name.to_owned())) {
+        fields.push(connection);
+        fields.push(provisioner);
+    }
+}
+
+/// Field of a resource (or provisioner) holding the shared `connection` block.
+fn connection_field(struct_name: &str, config: &mut CodeGeneratorConfig) -> Named<Format> {
+    config.add_comment(
+        &[struct_name, "connection"],
+        "How provisioners connect to the remote resource.",
+    );
+    config.add_annotation(
+        &[struct_name, "connection"],
+        deserialize_with("deserialize_block", true),
+    );
+    field("connection", optional(type_name("connection")))
+}
+
+/// Generate the `terraform` settings block of the config root, along with a constructor
+/// requiring the providers found in the schema, given by name along with their source address
+/// (e.g. `registry.terraform.io/hashicorp/aws`).
+fn generate_terraform_settings(
+    sources: &BTreeMap<&str, &str>,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let string = || serde_json::json!("string");
+
+    let required_provider = literal_block(
+        "Requirement of a provider, keyed by its local name.",
+        vec![
+            (
+                "source",
+                argument(
+                    string(),
+                    "Source address of the provider (e.g. `hashicorp/aws`).",
+                ),
+            ),
+            (
+                "version",
+                argument(string(), "Version constraint of the provider."),
+            ),
+        ],
+    );
+    let struct_name = "terraform_required_provider";
+    export_block(
+        None,
+        struct_name,
+        struct_name,
+        required_provider,
+        reg,
+        config,
+    )?;
+    config.add_comment(
+        &[struct_name, "configuration_aliases"],
+        "Aliased configurations of the provider expected by a module.",
+    );
+    if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, struct_name.to_owned())) {
+        let aliases = Format::Seq(Box::new(type_name("ProviderRef")));
+        fields.push(field("configuration_aliases", optional(aliases)));
+    }
+
+    let settings = literal_block(
+        "Settings of Terraform itself.",
+        vec![(
+            "required_version",
+            argument(string(), "Version constraint of Terraform."),
+        )],
+    );
+    export_block(None, "terraform", "terraform", settings, reg, config)?;
+    let settings_fields = [
+        (
+            "required_providers",
+            map(type_name(struct_name)),
+            "Providers required by the module, keyed by their local name.",
+        ),
+        (
+            "backend",
+            map(type_name("Dynamic")),
+            "Backend storing the state, keyed by its type (e.g. `s3`).",
+        ),
+    ];
+    for (name, value, description) in settings_fields.iter() {
+        config.add_comment(&["terraform", name], description);
+        config.add_annotation(
+            &["terraform", name],
+            deserialize_with("deserialize_labels", true),
+        );
+        if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, "terraform".to_owned()))
+        {
+            fields.push(field(name, optional(value.clone())));
+        }
+    }
+
+    let requirements = sources
+        .iter()
+        .map(|(name, source)| {
+            let source = match source.contains('/') {
+                true => format!("Some({:?}.to_owned())", source),
+                false => "None".to_owned(),
+            };
+            format!(
+                r#"            ({:?}.to_owned(), {} {{
+                source: {},
+                ..Default::default()
+            }}),
+"#,
+                name, struct_name, source
+            )
+        })
+        .collect::<String>();
+    config.add_custom_code(
+        &["terraform"],
+        &format!(
+            r#"impl terraform {{
+    /// Settings requiring the providers these definitions were generated for.
+    pub fn with_generated_providers() -> Self {{
+        let required_providers = vec![
+{requirements}        ];
+        terraform {{
+            required_providers: Some(required_providers.into_iter().collect()),
+            ..Default::default()
+        }}
+    }}
+}}"#,
+            requirements = requirements
+        ),
+    );
+
+    Ok(())
+}
+
+/// Generate the `provisioner` enum, with a variant (and struct) per provisioner type, along
+/// with the `connection` block shared by resources and provisioners.
+fn generate_provisioners(
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let string = || serde_json::json!("string");
+    let number = || serde_json::json!("number");
+    let boolean = || serde_json::json!("bool");
+    let strings = || serde_json::json!(["list", "string"]);
+
+    let connection = injected_block(
+        "How provisioners connect to the remote resource.",
+        vec![
+            (
+                "type",
+                argument(string(), "Connection type: `ssh` (default) or `winrm`."),
+            ),
+            ("user", argument(string(), "User to connect as.")),
+            (
+                "password",
+                sensitive_argument(string(), "Password of the user."),
+            ),
+            (
+                "host",
+                required_argument(string(), "Address of the resource to connect to."),
+            ),
+            ("port", argument(number(), "Port to connect to.")),
+            (
+                "timeout",
+                argument(string(), "Time to wait for the connection to be available."),
+            ),
+            (
+                "script_path",
+                argument(
+                    string(),
+                    "Path where scripts are copied to on the remote resource.",
+                ),
+            ),
+            (
+                "private_key",
+                sensitive_argument(string(), "Contents of the SSH key to use."),
+            ),
+            (
+                "certificate",
+                argument(
+                    string(),
+                    "Contents of a signed CA certificate, used along with `private_key`.",
+                ),
+            ),
+            (
+                "agent",
+                argument(
+                    boolean(),
+                    "Whether to use the SSH agent for authentication.",
+                ),
+            ),
+            (
+                "agent_identity",
+                argument(string(), "Preferred identity of the SSH agent."),
+            ),
+            (
+                "host_key",
+                argument(
+                    string(),
+                    "Public key of the remote host or of its signing CA.",
+                ),
+            ),
+            (
+                "target_platform",
+                argument(
+                    string(),
+                    "Platform of the remote resource: `unix` (default) or `windows`.",
+                ),
+            ),
+            (
+                "bastion_host",
+                argument(string(), "Bastion host to connect through."),
+            ),
+            (
+                "bastion_host_key",
+                argument(
+                    string(),
+                    "Public key of the bastion host or of its signing CA.",
+                ),
+            ),
+            (
+                "bastion_port",
+                argument(number(), "Port of the bastion host."),
+            ),
+            (
+                "bastion_user",
+                argument(string(), "User to connect to the bastion host as."),
+            ),
+            (
+                "bastion_password",
+                sensitive_argument(string(), "Password of the bastion user."),
+            ),
+            (
+                "bastion_private_key",
+                sensitive_argument(
+                    string(),
+                    "Contents of the SSH key to use for the bastion host.",
+                ),
+            ),
+            (
+                "bastion_certificate",
+                argument(
+                    string(),
+                    "Contents of a signed CA certificate, used along with `bastion_private_key`.",
+                ),
+            ),
+            (
+                "proxy_scheme",
+                argument(string(), "Scheme of the proxy: `http`, `https`, `socks5`."),
+            ),
+            (
+                "proxy_host",
+                argument(string(), "Proxy host to connect through."),
+            ),
+            ("proxy_port", argument(number(), "Port of the proxy host.")),
+            ("proxy_user_name", argument(string(), "User of the proxy.")),
+            (
+                "proxy_user_password",
+                sensitive_argument(string(), "Password of the proxy user."),
+            ),
+            (
+                "https",
+                argument(boolean(), "Whether to connect using HTTPS (WinRM)."),
+            ),
+            (
+                "insecure",
+                argument(
+                    boolean(),
+                    "Whether to skip validating the HTTPS certificate chain (WinRM).",
+                ),
+            ),
+            (
+                "use_ntlm",
+                argument(boolean(), "Whether to use NTLM authentication (WinRM)."),
+            ),
+            (
+                "cacert",
+                argument(string(), "CA certificate to validate against (WinRM)."),
+            ),
+        ],
+    );
+    export_block(None, "connection", "connection", connection, reg, config)?;
+
+    let provisioners = vec![
+        (
+            "local-exec",
+            injected_block(
+                "Runs a command on the machine running Terraform.",
+                vec![
+                    ("command", required_argument(string(), "Command to run.")),
+                    (
+                        "working_dir",
+                        argument(string(), "Directory the command is run in."),
+                    ),
+                    (
+                        "interpreter",
+                        argument(
+                            strings(),
+                            "Interpreter (and its arguments) the command is passed to.",
+                        ),
+                    ),
+                    (
+                        "environment",
+                        argument(
+                            serde_json::json!(["map", "string"]),
+                            "Environment variables of the command.",
+                        ),
+                    ),
+                    (
+                        "quiet",
+                        argument(boolean(), "Whether to hide the command from the output."),
+                    ),
+                ],
+            ),
+        ),
+        (
+            "remote-exec",
+            injected_block(
+                "Runs commands on the remote resource.",
+                vec![
+                    ("inline", argument(strings(), "Commands to run, in order.")),
+                    (
+                        "script",
+                        argument(
+                            string(),
+                            "Path of a local script copied to the resource and run.",
+                        ),
+                    ),
+                    (
+                        "scripts",
+                        argument(
+                            strings(),
+                            "Paths of local scripts copied to the resource and run, in order.",
+                        ),
+                    ),
+                ],
+            ),
+        ),
+        (
+            "file",
+            injected_block(
+                "Copies files or directories to the remote resource.",
+                vec![
+                    (
+                        "source",
+                        argument(string(), "Local file or directory to copy."),
+                    ),
+                    (
+                        "content",
+                        argument(string(), "Content to copy, instead of `source`."),
+                    ),
+                    (
+                        "destination",
+                        required_argument(string(), "Destination path on the remote resource."),
+                    ),
+                ],
+            ),
+        ),
+    ];
+
+    let mut variants = BTreeMap::new();
+    for (pos, (provisioner_type, mut blk)) in provisioners.into_iter().enumerate() {
+        let variant_name = provisioner_type.replace('-', "_");
+        let struct_name = format!("provisioner_{}", variant_name);
+        if let Some(attrs) = blk.attributes.as_mut() {
+            attrs.insert(
+                "when".to_owned(),
+                argument(string(), "When to run: `create` (default) or `destroy`."),
+            );
+            attrs.insert(
+                "on_failure".to_owned(),
+                argument(
+                    string(),
+                    "Whether to `fail` (default) or `continue` on failure.",
+                ),
+            );
+        }
+        export_block(None, &struct_name, &struct_name, blk, reg, config)?;
+        let connection = connection_field(&struct_name, config);
+        if let Some(ContainerFormat::Struct(fields)) = reg.get_mut(&(None, struct_name.clone())) {
+            fields.push(connection);
+        }
+
+        config.add_annotation(
+            &["provisioner", &variant_name],
+            format!(
+                "#[serde(rename = \"{}\", deserialize_with = \"tfschema_bindgen::runtime::deserialize_block\")]",
+                provisioner_type
+            ),
+        );
+        let variant = Named {
+            name: variant_name,
+            value: VariantFormat::NewType(Box::new(type_name(&struct_name))),
+        };
+        variants.insert(pos as u32, variant);
+    }
+    reg.insert(
+        (None, "provisioner".to_owned()),
+        ContainerFormat::Enum(variants),
+    );
+    config.add_comment(&["provisioner"], "Provisioner of a resource, by type.");
+
+    Ok(())
+}
+
+fn export_block(
+    namespace: Option<String>,
+    name: &str,
+    struct_name: &str,
+    blk: Block,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let attrs = blk.attributes.clone().unwrap_or_default();
+    let mut cf1 = export_attributes(namespace.as_ref(), name, &attrs, reg, config)?
+        .unwrap_or(ContainerFormat::Struct(Vec::new()));
+    export_attribute_docs(struct_name, &attrs, config);
+    if let Some(desc) = &blk.description {
+        let doc = doc_comment(desc, blk.description_kind.as_ref());
+        config.add_comment(&[struct_name], &doc);
+    }
+    if blk.deprecated == Some(true) {
+        let annotation = deprecated_annotation(blk.description.as_ref());
+        config.add_annotation(&[struct_name], annotation);
+    }
+    if let Some(bt) = &blk.block_types {
+        for (block_type_name, nested_block) in bt {
+            let field = export_block_type(
+                namespace.as_ref(),
+                name,
+                block_type_name,
+                nested_block,
+                reg,
+                config,
+            )?;
+            if let Some(desc) = &nested_block.block.description {
+                let doc = doc_comment(desc, nested_block.block.description_kind.as_ref());
+                config.add_comment(&[struct_name, &field.name], &doc);
+            }
+            if nested_block.block.deprecated == Some(true) {
+                let annotation = deprecated_annotation(nested_block.block.description.as_ref());
+                config.add_annotation(&[struct_name, &field.name], annotation);
+            }
+            if let Some(annotation) = block_field_annotation(&field.value) {
+                config.add_annotation(&[struct_name, &field.name], annotation);
+            }
+            if let ContainerFormat::Struct(ref mut fields) = cf1 {
+                fields.push(field);
+            }
+        }
+    }
+
+    reg.insert((None, struct_name.to_owned()), cf1);
+
+    Ok(())
+}
+
+fn export_block_type(
+    namespace: Option<&String>,
+    parent_name: &str,
+    name: &str,
+    blk: &NestedBlock,
+    reg: &mut Registry,
+    config: &mut CodeGeneratorConfig,
+) -> std::result::Result<Named<Format>, Box<dyn std::error::Error>> {
+    let block_type_ns = namespace.map_or_else(
+        || format!("{}_block_type", parent_name),
+        |v| format!("{}_{}_block_type", parent_name, v),
+    );
+    let block_type_fqn = namespace.map_or_else(
+        || format!("{}_block_type_{}", parent_name, name.to_owned()),
+        |v| format!("{}_{}_block_type_{}", parent_name, v, name.to_owned()),
+    );
+    let attrs = blk.block.attributes.clone().unwrap_or_default();
+    let mut nested_cf = export_attributes(None, &block_type_fqn, &attrs, reg, config)?
+        .unwrap_or(ContainerFormat::Struct(Vec::new()));
+    export_attribute_docs(&block_type_fqn, &attrs, config);
+    if let Some(desc) = &blk.block.description {
+        let doc = doc_comment(desc, blk.block.description_kind.as_ref());
+        config.add_comment(&[&block_type_fqn], &doc);
+    }
+    if blk.block.deprecated == Some(true) {
+        let annotation = deprecated_annotation(blk.block.description.as_ref());
+        config.add_annotation(&[&block_type_fqn], annotation);
+    }
+
+    // export inner block types, named after this one so they are unique to their parent
+    if let Some(bt) = &blk.block.block_types {
+        for (block_type_name, nested_block) in bt {
+            let field = export_block_type(
+                None,
+                &block_type_fqn,
+                block_type_name,
+                nested_block,
+                reg,
+                config,
+            )?;
+            if let Some(desc) = &nested_block.block.description {
+                let doc = doc_comment(desc, nested_block.block.description_kind.as_ref());
+                config.add_comment(&[&block_type_fqn, &field.name], &doc);
+            }
+            if nested_block.block.deprecated == Some(true) {
+                let annotation = deprecated_annotation(nested_block.block.description.as_ref());
+                config.add_annotation(&[&block_type_fqn, &field.name], annotation);
+            }
+            if let Some(annotation) = block_field_annotation(&field.value) {
+                config.add_annotation(&[&block_type_fqn, &field.name], annotation);
+            }
+            if let ContainerFormat::Struct(ref mut fields) = nested_cf {
+                fields.push(field);
+            }
+        }
+    }
+    reg.insert((Some(block_type_ns), name.to_owned()), nested_cf);
+
+    Ok(Named {
+        name: field_name(name),
+        value: export_nesting_mode(
+            blk.nesting_mode.as_ref(),
+            blk.min_items.unwrap_or(0) >= 1,
+            blk.max_items,
+            &block_type_fqn,
+        ),
+    })
+}
+
+/// Shape the field holding nested items of the given type after their nesting mode. Lists and
+/// sets of at most one item (how SDKv2 providers declare single blocks) are shaped as single
+/// blocks.
+fn export_nesting_mode(
+    nesting_mode: Option<&String>,
+    required: bool,
+    max_items: Option<u16>,
+    fqn: &str,
+) -> Format {
+    let item = Format::TypeName(fqn.to_owned());
+    let mode = match nesting_mode.map(String::as_str) {
+        Some("list") | Some("set") if max_items == Some(1) => Some("single"),
+        mode => mode,
+    };
+    match mode {
+        Some("single") | Some("group") if required => item,
+        Some("single") | Some("group") => Format::Option(Box::new(item)),
+        Some("set") => Format::TypeName(format!("Set<{}>", fqn)),
+        Some("map") => Format::Map {
+            key: Box::new(Format::Str),
+            value: Box::new(item),
+        },
+        _ => Format::Seq(Box::new(item)),
+    }
+}
+
+pub fn read_tf_schema_from_file<P: AsRef<Path>>(
+    path: P,
+) -> std::result::Result<TerraformSchemaExport, Box<dyn std::error::Error>> {
+    // Open the file in read-only mode with buffer.
+    let file = File::open(path).expect("input file must be readable");
+    let reader = BufReader::new(file);
+    // Read the JSON contents of the file as an instance of `User`.
+    let d: TerraformSchemaExport = serde_json::from_reader(reader)?;
+
+    // Return the `Diagram`.
+    Ok(d)
+}
+
+#[cfg(test)]
+#[allow(clippy::get_first, clippy::bool_assert_comparison)]
+mod test {
+    use super::*;
+    use crate::test_utils::{config, datasource_root, provider_root, resource_root};
+    use std::fs::File;
+    use std::process::Command;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_deserialize_example_tf_schema() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+
+        assert!(tf_schema.is_ok());
+        let test_schema = tf_schema
+            .as_ref()
+            .unwrap()
+            .provider_schemas
             .get("test_provider");
 
-        assert_eq!(tf_schema.as_ref().unwrap().provider_schemas.len(), 1);
-        assert!(test_schema.is_some());
+        assert_eq!(tf_schema.as_ref().unwrap().provider_schemas.len(), 1);
+        assert!(test_schema.is_some());
+        assert_eq!(
+            test_schema
+                .unwrap()
+                .data_source_schemas
+                .as_ref()
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            test_schema.map(|x| x.resource_schemas.is_none()),
+            Some(false)
+        );
+    }
+
+    #[test]
+    fn test_generate_registry_from_schema() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap());
+
+        assert!(registry.is_ok());
+        assert_eq!(registry.unwrap().len(), 68);
+    }
+
+    #[test]
+    fn test_export_collection_element_types() {
+        let mut reg = Registry::new();
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let name = (None, "test".to_owned());
+        assert_eq!(
+            export_type(
+                &serde_json::json!(["list", "number"]),
+                &name,
+                &mut reg,
+                &mut config
+            )
+            .unwrap(),
+            Format::Seq(Box::new(Format::I64))
+        );
+        assert_eq!(
+            export_type(
+                &serde_json::json!(["map", ["list", "string"]]),
+                &name,
+                &mut reg,
+                &mut config
+            )
+            .unwrap(),
+            Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(Format::Seq(Box::new(Format::Str))),
+            }
+        );
+        assert_eq!(
+            export_type(
+                &serde_json::json!(["set", ["map", "bool"]]),
+                &name,
+                &mut reg,
+                &mut config
+            )
+            .unwrap(),
+            Format::Seq(Box::new(Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(Format::Bool),
+            }))
+        );
+        assert_eq!(
+            export_type(
+                &serde_json::json!(["map", "dynamic"]),
+                &name,
+                &mut reg,
+                &mut config
+            )
+            .unwrap(),
+            Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(Format::TypeName("Dynamic".to_owned())),
+            }
+        );
+        assert!(export_type(
+            &serde_json::json!(["list", "unknown"]),
+            &name,
+            &mut reg,
+            &mut config
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_export_tuple_types() {
+        let mut reg = Registry::new();
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let name = (Some("test_object_type".to_owned()), "pair".to_owned());
+        assert_eq!(
+            export_type(
+                &serde_json::json!(["tuple", ["string", ["list", "number"], ["object", {}]]]),
+                &name,
+                &mut reg,
+                &mut config
+            )
+            .unwrap(),
+            Format::Tuple(vec![
+                Format::Str,
+                Format::Seq(Box::new(Format::I64)),
+                Format::TypeName("test_object_type_pair_2".to_owned()),
+            ])
+        );
+        assert_eq!(
+            reg.get(&(Some("test_object_type".to_owned()), "pair_2".to_owned())),
+            Some(&ContainerFormat::Struct(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_export_object_types() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap()).unwrap();
+
+        let details = registry.get(&(None, "test_resource_b_details".to_owned()));
+        assert!(
+            matches!(details, Some(ContainerFormat::Struct(fields)) if fields.contains(&Named {
+                name: "settings".to_owned(),
+                value: Format::Option(Box::new(Format::TypeName(
+                    "test_resource_b_resource_object_type_settings".to_owned()
+                ))),
+            }))
+        );
+        let settings = registry.get(&(
+            Some("test_resource_b_resource_object_type".to_owned()),
+            "settings".to_owned(),
+        ));
+        assert_eq!(
+            settings,
+            Some(&ContainerFormat::Struct(vec![
+                Named {
+                    name: "enabled".to_owned(),
+                    value: Format::Bool,
+                },
+                Named {
+                    name: "retries".to_owned(),
+                    value: Format::Option(Box::new(Format::I64)),
+                },
+                Named {
+                    name: "window".to_owned(),
+                    value: Format::TypeName(
+                        "test_resource_b_resource_object_type_settings_object_type_window"
+                            .to_owned()
+                    ),
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_export_nesting_modes() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap()).unwrap();
+        let details = match registry.get(&(None, "test_resource_b_details".to_owned())) {
+            Some(ContainerFormat::Struct(fields)) => fields,
+            _ => panic!("missing test_resource_b_details"),
+        };
+        let field = |name: &str| details.iter().find(|f| f.name == name).map(|f| &f.value);
+
+        assert_eq!(
+            field("timeouts"),
+            Some(&Format::Option(Box::new(Format::TypeName(
+                "test_resource_b_resource_block_type_timeouts".to_owned()
+            ))))
+        );
+        assert_eq!(
+            field("target"),
+            Some(&Format::Seq(Box::new(Format::TypeName(
+                "test_resource_b_resource_block_type_target".to_owned()
+            ))))
+        );
+        assert_eq!(
+            field("tag"),
+            Some(&Format::TypeName(
+                "Set<test_resource_b_resource_block_type_tag>".to_owned()
+            ))
+        );
+        assert_eq!(
+            field("selector"),
+            Some(&Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(Format::TypeName(
+                    "test_resource_b_resource_block_type_selector".to_owned()
+                )),
+            })
+        );
+        let selector = details.iter().find(|f| f.name == "selector").unwrap();
+        assert_eq!(
+            block_field_annotation(&selector.value),
+            Some(deserialize_with("deserialize_labels", false))
+        );
+        assert_eq!(
+            field("network"),
+            Some(&Format::Option(Box::new(Format::TypeName(
+                "test_resource_b_resource_block_type_network".to_owned()
+            ))))
+        );
+        let network = details.iter().find(|f| f.name == "network").unwrap();
+        assert_eq!(
+            block_field_annotation(&network.value),
+            Some(deserialize_with("deserialize_block", true))
+        );
+        assert_eq!(
+            export_nesting_mode(Some(&"single".to_owned()), true, None, "test"),
+            Format::TypeName("test".to_owned())
+        );
+        assert_eq!(
+            export_nesting_mode(Some(&"set".to_owned()), true, Some(1), "test"),
+            Format::TypeName("test".to_owned())
+        );
+        assert_eq!(
+            export_nesting_mode(Some(&"list".to_owned()), false, Some(2), "test"),
+            Format::Seq(Box::new(Format::TypeName("test".to_owned())))
+        );
+    }
+
+    #[test]
+    fn test_export_nested_attribute_types() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap()).unwrap();
+        let details = match registry.get(&(None, "test_resource_b_details".to_owned())) {
+            Some(ContainerFormat::Struct(fields)) => fields,
+            _ => panic!("missing test_resource_b_details"),
+        };
+        let field = |name: &str| details.iter().find(|f| f.name == name).map(|f| &f.value);
+
+        assert_eq!(
+            field("endpoints"),
+            Some(&Format::Option(Box::new(Format::Seq(Box::new(
+                Format::TypeName("test_resource_b_resource_object_type_endpoints".to_owned())
+            )))))
+        );
+        assert_eq!(
+            field("auth"),
+            Some(&Format::TypeName(
+                "test_resource_b_resource_object_type_auth".to_owned()
+            ))
+        );
+        assert_eq!(
+            registry.get(&(
+                Some("test_resource_b_resource_object_type".to_owned()),
+                "endpoints".to_owned()
+            )),
+            Some(&ContainerFormat::Struct(vec![
+                Named {
+                    name: "host".to_owned(),
+                    value: Format::Str,
+                },
+                Named {
+                    name: "port".to_owned(),
+                    value: Format::Option(Box::new(Format::I64)),
+                },
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_generate_doc_comments() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        export_test_schema(&mut config);
+        let comment = |path: &[&str]| config.comments[&config.qualified_name(path)].clone();
+
+        assert_eq!(
+            comment(&["test_provider_details", "base_url"]),
+            "The url.\n"
+        );
+        assert_eq!(comment(&["test_resource_b_details"]), "Resource B.\n");
+        assert_eq!(
+            comment(&["test_resource_b_details", "timeouts"]),
+            "Operation timeouts.\n"
+        );
+        assert_eq!(
+            comment(&["test_resource_b_resource_object_type_auth", "username"]),
+            "Name of the user.\n"
+        );
+        let source = generated_source(CodeGeneratorConfig::new("test".to_owned()));
+        assert!(source.contains("    /// Name of the user.\n"));
+    }
+
+    #[test]
+    fn test_export_lifecycle_block() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap()).unwrap();
+        let lifecycle_type = "test_resource_b_resource_block_type_lifecycle";
+        let condition_type =
+            "test_resource_b_resource_block_type_lifecycle_block_type_precondition";
+
+        let resource = &registry[&(None, "test_resource_b_details".to_owned())];
+        assert!(
+            matches!(resource, ContainerFormat::Struct(fields) if fields.contains(&Named {
+                name: "lifecycle".to_owned(),
+                value: Format::Option(Box::new(Format::TypeName(lifecycle_type.to_owned()))),
+            }))
+        );
+        let lifecycle = &registry[&(
+            Some("test_resource_b_resource_block_type".to_owned()),
+            "lifecycle".to_owned(),
+        )];
+        let string_list = Format::Seq(Box::new(Format::Str));
+        assert_eq!(
+            lifecycle,
+            &ContainerFormat::Struct(vec![
+                Named {
+                    name: "create_before_destroy".to_owned(),
+                    value: Format::Option(Box::new(Format::Bool)),
+                },
+                Named {
+                    name: "ignore_changes".to_owned(),
+                    value: Format::Option(Box::new(Format::TypeName("IgnoreChanges".to_owned()))),
+                },
+                Named {
+                    name: "prevent_destroy".to_owned(),
+                    value: Format::Option(Box::new(Format::Bool)),
+                },
+                Named {
+                    name: "replace_triggered_by".to_owned(),
+                    value: Format::Option(Box::new(string_list)),
+                },
+                Named {
+                    name: "postcondition".to_owned(),
+                    value: Format::Seq(Box::new(Format::TypeName(
+                        "test_resource_b_resource_block_type_lifecycle_block_type_postcondition"
+                            .to_owned()
+                    ))),
+                },
+                Named {
+                    name: "precondition".to_owned(),
+                    value: Format::Seq(Box::new(Format::TypeName(condition_type.to_owned()))),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_export_data_source_meta_arguments() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap()).unwrap();
+        let field_names = |ns: Option<&str>, name: &str| match &registry
+            [&(ns.map(String::from), name.to_owned())]
+        {
+            ContainerFormat::Struct(fields) => {
+                fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>()
+            }
+            _ => Vec::new(),
+        };
+
+        let data_source = field_names(None, "test_data_source_b_details");
+        for meta_argument in &["count", "depends_on", "for_each", "provider", "lifecycle"] {
+            assert!(data_source.contains(&meta_argument.to_string()));
+        }
+        assert!(!data_source.contains(&"provisioner".to_owned()));
+        for (meta_argument, runtime_type) in &[
+            ("count", "Count"),
+            ("for_each", "ForEach"),
+            ("provider", "ProviderRef"),
+        ] {
+            assert!(matches!(
+                &registry[&(None, "test_data_source_b_details".to_owned())],
+                ContainerFormat::Struct(fields) if fields.contains(&Named {
+                    name: meta_argument.to_string(),
+                    value: Format::Option(Box::new(Format::TypeName(runtime_type.to_string()))),
+                })
+            ));
+        }
+        let provider = field_names(None, "test_provider_details");
+        assert!(provider.contains(&"alias".to_owned()));
+        assert_eq!(
+            field_names(
+                Some("test_data_source_b_data_source_block_type"),
+                "lifecycle"
+            ),
+            vec!["postcondition", "precondition"]
+        );
+    }
+
+    #[test]
+    fn test_export_provisioners() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_test_schema(&mut config);
+
+        let resource = &registry[&(None, "test_resource_a_details".to_owned())];
+        assert!(
+            matches!(resource, ContainerFormat::Struct(fields) if fields.ends_with(&[
+                Named {
+                    name: "connection".to_owned(),
+                    value: Format::Option(Box::new(Format::TypeName("connection".to_owned()))),
+                },
+                Named {
+                    name: "provisioner".to_owned(),
+                    value: Format::Seq(Box::new(Format::TypeName("provisioner".to_owned()))),
+                },
+            ]))
+        );
+        assert!(matches!(
+            &registry[&(None, "connection".to_owned())],
+            ContainerFormat::Struct(fields) if fields.iter().any(|f| f.name == "bastion_host")
+        ));
+        assert!(matches!(
+            &registry[&(None, "provisioner".to_owned())],
+            ContainerFormat::Enum(variants) if variants.len() == 3
+        ));
+        assert_eq!(
+            field_format(&registry, None, "provisioner_local_exec", "command"),
+            Some(&Format::Str)
+        );
+        assert_eq!(
+            config.annotations[&config.qualified_name(&["provisioner", "local_exec"])],
+            vec!["#[serde(rename = \"local-exec\", deserialize_with = \"tfschema_bindgen::runtime::deserialize_block\")]"]
+        );
+    }
+
+    #[test]
+    fn test_doc_comment_escaping() {
+        assert_eq!(
+            doc_comment("Use [\"ALL_CLIENTS\"] when <unsure>.", None),
+            "Use \\[\"ALL_CLIENTS\"\\] when \\<unsure\\>."
+        );
+        assert_eq!(
+            doc_comment("  # not a heading\n    not code", Some(&StringKind::Plain)),
+            "\\# not a heading\nnot code"
+        );
+        assert_eq!(
+            doc_comment(
+                "See [docs](/docs/providers/index.html) or [Policy][1], not [x] nor `[y]`.",
+                Some(&StringKind::Markdown)
+            ),
+            "See [docs](https://www.terraform.io/docs/providers/index.html) or [Policy][1], not \\[x\\] nor `[y]`."
+        );
+        assert_eq!(
+            doc_comment(
+                "Example:\n```\nresource \"a\" \"b\" {}\n```\n\n    provider \"c\" {}\n\nDone.",
+                Some(&StringKind::Markdown)
+            ),
+            "Example:\n```hcl\nresource \"a\" \"b\" {}\n```\n\n```hcl\nprovider \"c\" {}\n\n```\nDone."
+        );
+    }
+
+    #[test]
+    fn test_generate_deprecated_annotations() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        export_test_schema(&mut config);
+        let annotations = |path: &[&str]| config.annotations[&config.qualified_name(path)].clone();
+        let replaced = "#[deprecated(note = \"Use \\\"test_data_source_a\\\" instead.\")]";
+
         assert_eq!(
-            test_schema
-                .unwrap()
-                .data_source_schemas
-                .as_ref()
-                .unwrap()
-                .len(),
-            2
+            annotations(&["test_resource_b_details", "status"]),
+            vec!["#[deprecated(note = \"Policy Status: ACTIVE or INACTIVE.\")]"]
         );
         assert_eq!(
-            test_schema.map(|x| x.resource_schemas.is_none()),
-            Some(false)
+            annotations(&["test_resource_b_details", "selector"]),
+            vec![
+                "#[deprecated]".to_owned(),
+                deserialize_with("deserialize_labels", false)
+            ]
+        );
+        assert_eq!(annotations(&["test_data_source_b_details"]), vec![replaced]);
+        assert_eq!(
+            annotations(&["data_root", "test_data_source_b"]),
+            vec![
+                replaced.to_owned(),
+                deserialize_with("deserialize_labeled_blocks", false)
+            ]
         );
+        let source = generated_source(CodeGeneratorConfig::new("test".to_owned()));
+        assert!(source.contains(&format!("{}\n#[derive(", replaced)));
     }
 
     #[test]
-    fn test_generate_registry_from_schema() {
-        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
-        let registry = export_schema_to_registry(&tf_schema.as_ref().unwrap());
+    fn test_generate_sensitive_wrappers() {
+        let config = CodeGeneratorConfig::new("test".to_owned())
+            .with_sensitive_wrapper(true)
+            .with_zeroize_sensitive(true);
+        let registry = export_test_schema(&mut config.clone());
+        let resource = |name| field_format(&registry, None, "test_resource_b_details", name);
 
-        assert!(registry.is_ok());
-        assert_eq!(registry.unwrap().len(), 10);
+        assert_eq!(
+            field_format(&registry, None, "test_provider_details", "api_token"),
+            Some(&type_name("Secret<String>"))
+        );
+        assert_eq!(
+            field_format(
+                &registry,
+                Some("test_resource_b_resource_object_type"),
+                "auth",
+                "password"
+            ),
+            Some(&optional(type_name("Secret<String>")))
+        );
+        assert_eq!(
+            resource("labels"),
+            Some(&optional(type_name("Sensitive<Map<String, bool>>")))
+        );
+        assert!(generated_source(config)
+            .contains("use tfschema_bindgen::runtime::{Change, Count, Dynamic, Expr, ForEach, IgnoreChanges, ProviderRef, Sensitive, Set, Secret};"));
+
+        // sensitive values are left unwrapped by default
+        let registry = export_test_schema(&mut CodeGeneratorConfig::new("test".to_owned()));
+        assert_eq!(
+            field_format(&registry, None, "test_provider_details", "api_token"),
+            Some(&Format::Str)
+        );
     }
 
     #[test]
-    fn test_generate_serde_model_from_registry() {
+    fn test_generate_expression_wrappers() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned())
+            .with_sensitive_wrapper(true)
+            .with_expression_wrapper(true);
+        let registry = export_test_schema(&mut config);
+        let resource = |name| field_format(&registry, None, "test_resource_b_details", name);
+
+        assert_eq!(
+            resource("priority"),
+            Some(&optional(type_name("Expr<i64>")))
+        );
+        assert_eq!(
+            resource("ports"),
+            Some(&optional(type_name("Expr<Vec<Expr<i64>>>")))
+        );
+        assert_eq!(
+            resource("groups_included"),
+            Some(&optional(type_name("Expr<Vec<Expr<String>>>")))
+        );
+        assert_eq!(
+            resource("labels"),
+            Some(&optional(type_name(
+                "Sensitive<Expr<Map<String, Expr<bool>>>>"
+            )))
+        );
+        assert_eq!(
+            resource("auth"),
+            Some(&type_name(
+                "Expr<test_resource_b_resource_object_type_auth>"
+            ))
+        );
+        // strings and dynamic values are left unwrapped
+        assert_eq!(
+            field_format(&registry, None, "test_provider_details", "api_token"),
+            Some(&type_name("Sensitive<String>"))
+        );
+        assert_eq!(resource("manifest"), Some(&optional(type_name("Dynamic"))));
+
+        // meta-arguments and settings must be literals
+        assert_eq!(
+            resource("depends_on"),
+            Some(&optional(Format::Seq(Box::new(Format::Str))))
+        );
+        let lifecycle = Some("test_resource_b_resource_block_type");
+        assert_eq!(
+            field_format(&registry, lifecycle, "lifecycle", "prevent_destroy"),
+            Some(&optional(Format::Bool))
+        );
+        for (container, name) in &[("variable", "sensitive"), ("output", "sensitive")] {
+            assert_eq!(
+                field_format(&registry, None, container, name),
+                Some(&optional(Format::Bool))
+            );
+        }
+        assert_eq!(
+            field_format(&registry, None, "output", "depends_on"),
+            Some(&optional(Format::Seq(Box::new(Format::Str))))
+        );
+    }
+
+    #[test]
+    fn test_generate_reference_builders() {
+        let source = generated_source(
+            CodeGeneratorConfig::new("test".to_owned())
+                .with_sensitive_wrapper(true)
+                .with_expression_wrapper(true),
+        );
+        assert!(source.contains("pub mod test_resource_b {"));
+        assert!(source.contains("pub mod data_test_data_source_a {"));
+        assert!(source.contains("        Reference(format!(\"data.test_data_source_a.{}\", name))"));
+        // computed attributes are referenced too, without their wrappers
+        assert!(source.contains(
+            "        pub fn attr_arn(&self) -> tfschema_bindgen::runtime::Expr<String> {"
+        ));
+        assert!(source.contains(
+            "            tfschema_bindgen::runtime::Expr::interpolate(&format!(\"{}.arn\", self.0))"
+        ));
+        assert!(source.contains(
+            "        pub fn attr_labels(&self) -> tfschema_bindgen::runtime::Expr<Map<String, Expr<bool>>> {"
+        ));
+    }
+
+    #[test]
+    fn test_generate_resource_impls() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_separate_state(true);
+        export_test_schema(&mut config);
+        let custom_code = |name: &str| config.custom_code[&config.qualified_name(&[name])].clone();
+
+        for struct_name in &["test_resource_b_config", "test_resource_b_state"] {
+            let code = custom_code(struct_name);
+            assert!(code.contains(&format!(
+                "impl tfschema_bindgen::runtime::Resource for {} {{",
+                struct_name
+            )));
+            assert!(code.contains("    const TYPE_NAME: &'static str = \"test_resource_b\";"));
+            assert!(code.contains("    const KIND: tfschema_bindgen::runtime::ResourceKind = tfschema_bindgen::runtime::ResourceKind::Resource;"));
+            assert!(code.contains("    const PROVIDER: &'static str = \"test_provider\";"));
+        }
+        assert!(custom_code("test_provider_details")
+            .contains("impl tfschema_bindgen::runtime::Resource for test_provider_details {"));
+        assert!(custom_code("test_resource_b_state").contains(
+            "            name: \"arn\",\n            required: false,\n            optional: false,\n            computed: true,"
+        ));
+        assert!(custom_code("test_provider_details").contains(
+            "            name: \"api_token\",\n            required: true,\n            optional: false,\n            computed: false,\n            sensitive: true,"
+        ));
+    }
+
+    #[test]
+    fn test_generate_config_blocks() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_test_schema(&mut config);
+        let labeled = |item| {
+            optional(Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(type_name(item)),
+            })
+        };
+
+        assert_eq!(
+            field_format(&registry, None, "config", "variable"),
+            Some(&labeled("variable"))
+        );
+        assert_eq!(
+            config.annotations[&config.qualified_name(&["config", "variable"])],
+            vec![deserialize_with("deserialize_labels", true)]
+        );
+        assert_eq!(
+            field_format(&registry, None, "config", "locals"),
+            Some(&labeled("Dynamic"))
+        );
+        assert_eq!(
+            field_format(&registry, None, "variable", "validation"),
+            Some(&Format::Seq(Box::new(type_name(
+                "variable_block_type_validation"
+            ))))
+        );
+        assert_eq!(
+            field_format(&registry, None, "output", "value"),
+            Some(&type_name("Dynamic"))
+        );
+        assert_eq!(
+            field_format(&registry, None, "module", "providers"),
+            Some(&labeled("ProviderRef"))
+        );
+        // inputs of modules are the remaining keys of the block
+        assert_eq!(
+            field_format(&registry, None, "module", "inputs"),
+            Some(&Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(type_name("Dynamic")),
+            })
+        );
+        assert_eq!(
+            config.annotations[&config.qualified_name(&["module", "inputs"])],
+            vec!["#[serde(flatten)]"]
+        );
+    }
+
+    #[test]
+    fn test_generate_terraform_settings() {
+        let mut tf_schema =
+            read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json").unwrap();
+        let provider = tf_schema.provider_schemas.remove("test_provider").unwrap();
+        tf_schema.provider_schemas.insert(
+            "registry.terraform.io/acme/test_provider".to_owned(),
+            provider,
+        );
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_schema_with_config(&tf_schema, &mut config).unwrap();
+        let labeled = |item| {
+            optional(Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(type_name(item)),
+            })
+        };
+
+        assert_eq!(
+            field_format(&registry, None, "config", "terraform"),
+            Some(&optional(type_name("terraform")))
+        );
+        assert_eq!(
+            field_format(&registry, None, "terraform", "required_providers"),
+            Some(&labeled("terraform_required_provider"))
+        );
+        assert_eq!(
+            field_format(&registry, None, "terraform", "backend"),
+            Some(&labeled("Dynamic"))
+        );
+        assert_eq!(
+            field_format(
+                &registry,
+                None,
+                "terraform_required_provider",
+                "configuration_aliases"
+            ),
+            Some(&optional(Format::Seq(Box::new(type_name("ProviderRef")))))
+        );
+        // providers are required by their full source address
+        assert!(config.custom_code[&config.qualified_name(&["terraform"])]
+            .contains("source: Some(\"registry.terraform.io/acme/test_provider\".to_owned()),"));
+    }
+
+    #[test]
+    fn test_export_config_and_state_structs() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_separate_state(true);
+        let registry = export_test_schema(&mut config);
+        let field_names = |name: &str, ns: Option<&str>| match &registry
+            [&(ns.map(String::from), name.to_owned())]
+        {
+            ContainerFormat::Struct(fields) => {
+                fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>()
+            }
+            _ => Vec::new(),
+        };
+
+        assert!(!registry.contains_key(&(None, "test_resource_b_details".to_owned())));
+        let input = field_names("test_resource_b_config", None);
+        assert!(input.contains(&"id".to_owned()) && input.contains(&"count".to_owned()));
+        assert!(!input.contains(&"arn".to_owned()));
+        let state = field_names("test_resource_b_state", None);
+        assert!(state.contains(&"id".to_owned()) && state.contains(&"arn".to_owned()));
+        assert!(!state.contains(&"count".to_owned()));
+
+        // nested blocks are split too
+        let ns = "test_resource_b_config_resource_block_type";
+        assert_eq!(field_names("target", Some(ns)), vec!["arn"]);
+        let ns = "test_resource_b_state_resource_block_type";
+        assert_eq!(field_names("target", Some(ns)), vec!["arn", "resolved_at"]);
+
+        let input = field_names("test_data_source_a_config", None);
+        assert!(!input.contains(&"users".to_owned()));
+        assert!(matches!(
+            &registry[&(None, "resource_root".to_owned())],
+            ContainerFormat::Enum(variants) if variants.values().any(|v| v.name == "test_resource_b"
+                && v.value == VariantFormat::NewType(Box::new(type_name(
+                    "Vec<Map<String, Vec<test_resource_b_config>>>"
+                ))))
+        ));
+    }
+
+    #[test]
+    fn test_export_two_level_blocks_in_config_and_state() {
+        let computed_leaf = serde_json::json!({"nesting_mode": "single", "block": {
+            "attributes": {
+                "path": {"type": "string", "optional": true},
+                "computed_id": {"type": "string", "computed": true}
+            }
+        }});
+        let block = serde_json::json!({
+            "block_types": {"rule": {"nesting_mode": "list", "block": {
+                "attributes": {"name": {"type": "string", "optional": true}},
+                "block_types": {"match": computed_leaf}
+            }}}
+        });
+        let schema: TerraformSchemaExport = serde_json::from_value(serde_json::json!({
+            "format_version": "0.1",
+            "provider_schemas": {"p": {
+                "provider": {"version": 0, "block": {}},
+                "resource_schemas": {"p_a": {"version": 0, "block": block}}
+            }}
+        }))
+        .unwrap();
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_separate_state(true);
+        let registry = export_schema_with_config(&schema, &mut config).unwrap();
+        let field_names =
+            |ns: &str, name: &str| match &registry[&(Some(ns.to_owned()), name.to_owned())] {
+                ContainerFormat::Struct(fields) => {
+                    fields.iter().map(|f| f.name.clone()).collect::<Vec<_>>()
+                }
+                _ => Vec::new(),
+            };
+
+        let ns = "p_a_config_resource_block_type_rule_block_type";
+        assert_eq!(field_names(ns, "match"), vec!["path"]);
+        let ns = "p_a_state_resource_block_type_rule_block_type";
+        assert_eq!(field_names(ns, "match"), vec!["computed_id", "path"]);
+        assert!(!registry.contains_key(&(
+            Some("rule_resource_block_type".to_owned()),
+            "match".to_owned()
+        )));
+    }
+
+    #[test]
+    fn test_generate_state_model() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_separate_state(true);
+        let registry = export_test_schema(&mut config);
+
+        assert_eq!(
+            field_format(&registry, None, "state_resource", "r#type"),
+            Some(&Format::Str)
+        );
+        assert!(matches!(
+            &registry[&(None, "resource_values".to_owned())],
+            ContainerFormat::Enum(variants) if variants.values().any(|v| v.name == "test_resource_b"
+                && v.value == VariantFormat::NewType(Box::new(type_name("test_resource_b_state"))))
+        ));
+        assert!(matches!(
+            &registry[&(None, "data_values".to_owned())],
+            ContainerFormat::Enum(variants) if variants.values().any(|v| v.name == "test_data_source_a"
+                && v.value == VariantFormat::NewType(Box::new(type_name("test_data_source_a_state"))))
+        ));
+        let code = &config.custom_code[&config.qualified_name(&["state_resource"])];
+        assert!(code.contains(
+            "            (\"managed\", \"test_resource_b\") => Some(self.values.clone().unwrap_or_default().decode().map(resource_values::test_resource_b)),"
+        ));
+        assert!(code.contains(
+            "            (\"data\", \"test_data_source_a\") => Some(self.values.clone().unwrap_or_default().decode().map(data_values::test_data_source_a)),"
+        ));
+    }
+
+    #[test]
+    fn test_generate_plan_model() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_test_schema(&mut config);
+
+        assert_eq!(
+            field_format(&registry, None, "plan", "resource_changes"),
+            Some(&Format::Seq(Box::new(type_name("plan_resource_change"))))
+        );
+        assert_eq!(
+            field_format(&registry, None, "plan_change", "after_unknown"),
+            Some(&optional(type_name("Dynamic")))
+        );
+        assert!(matches!(
+            &registry[&(None, "resource_change_values".to_owned())],
+            ContainerFormat::Enum(variants) if variants.values().any(|v| v.name == "test_resource_b"
+                && v.value == VariantFormat::NewType(Box::new(type_name("Change<test_resource_b_state>"))))
+        ));
+        // required in config, but possibly only known after apply in plans
+        assert_eq!(
+            field_format(&registry, None, "test_resource_b_state", "name"),
+            Some(&optional(Format::Str))
+        );
+        assert!(config.custom_code[&config.qualified_name(&["plan_resource_change"])].contains(
+            "            (\"managed\", \"test_resource_b\") => Some(self.change.decode().map(resource_change_values::test_resource_b)),"
+        ));
+    }
+
+    #[test]
+    fn test_generate_object_layout() {
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_object_layout(true);
+        let registry = export_test_schema(&mut config);
+
+        assert_eq!(
+            field_format(&registry, None, "config", "resource"),
+            Some(&type_name("resource_root"))
+        );
+        assert_eq!(
+            field_format(&registry, None, "resource_root", "test_resource_b"),
+            Some(&optional(Format::Map {
+                key: Box::new(Format::Str),
+                value: Box::new(type_name("test_resource_b_details")),
+            }))
+        );
+        assert_eq!(
+            field_format(&registry, None, "provider_root", "test_provider"),
+            Some(&optional(Format::Seq(Box::new(type_name(
+                "test_provider_details"
+            )))))
+        );
+        assert!(
+            config.custom_code[&config.qualified_name(&["resource_root"])]
+                .starts_with("impl resource_root {\n    /// Whether no item is declared.")
+        );
+    }
+
+    #[test]
+    fn test_quickstart_bindings_are_up_to_date() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/quickstart-schema.json");
+        // as generated by `tfbindgen`
+        let mut config = CodeGeneratorConfig::new("default".to_owned());
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config).unwrap();
+        let mut out = Vec::new();
+        generate_serde_with_config(&config, &mut out, &registry).unwrap();
+        let source = String::from_utf8(out).unwrap();
+        assert_eq!(source, include_str!("../examples/quickstart/test.rs"));
+
+        // the excerpt of the crate documentation (and README) too
+        let docs = include_str!("lib.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("//!"))
+            .map(|line| line.strip_prefix(' ').unwrap_or(line))
+            .collect::<Vec<_>>();
+        let excerpt = docs
+            .iter()
+            .skip_while(|line| **line != "```rust,ignore")
+            .skip(1)
+            .take_while(|line| **line != "```")
+            .map(|line| format!("{}\n", line))
+            .collect::<String>();
+        assert!(excerpt.starts_with("#![allow("));
+        for part in excerpt.split("// ...\n") {
+            assert!(source.contains(part), "not generated:\n{}", part);
+        }
+    }
+
+    /// Export the test provider schema with `config`.
+    fn export_test_schema(config: &mut CodeGeneratorConfig) -> Registry {
         let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
-        let registry = export_schema_to_registry(&tf_schema.as_ref().unwrap());
+        export_schema_with_config(tf_schema.as_ref().unwrap(), config).unwrap()
+    }
+
+    /// Source generated from the test provider schema with `config`.
+    fn generated_source(mut config: CodeGeneratorConfig) -> String {
+        let registry = export_test_schema(&mut config);
+        let mut out = Vec::new();
+        generate_serde_with_config(&config, &mut out, &registry).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    /// Format of a field of a struct of the registry.
+    fn field_format<'a>(
+        registry: &'a Registry,
+        namespace: Option<&str>,
+        container: &str,
+        name: &str,
+    ) -> Option<&'a Format> {
+        match registry.get(&(namespace.map(String::from), container.to_owned())) {
+            Some(ContainerFormat::Struct(fields)) => {
+                fields.iter().find(|f| f.name == name).map(|f| &f.value)
+            }
+            _ => None,
+        }
+    }
+
+    /// Build the generated source as a crate depending on this one.
+    fn assert_generated_code_compiles(config: &CodeGeneratorConfig, registry: &Registry) {
+        assert_generated_crate(config, registry, "build", "");
+    }
+
+    /// Run `tests` (as an integration test of the generated crate, which they may `use`)
+    /// against the generated code.
+    fn assert_generated_code_runs(config: &CodeGeneratorConfig, registry: &Registry, tests: &str) {
+        assert_generated_crate(config, registry, "test", tests);
+    }
+
+    fn assert_generated_crate(
+        config: &CodeGeneratorConfig,
+        registry: &Registry,
+        command: &str,
+        tests: &str,
+    ) {
         let dir = tempdir().unwrap();
 
         std::fs::write(
@@ -452,20 +3191,33 @@ mod test {
     [dependencies]
     serde = { version = "1.0", features = ["derive"] }
     serde_bytes = "0.11"
+    tfschema-bindgen = { path = "$CRATE_DIR", default-features = false, features = ["zeroize"] }
+
+    [dev-dependencies]
+    serde_json = "1.0"
 
     [workspace]
-    "#,
+    "#
+            .replace("$CRATE_DIR", env!("CARGO_MANIFEST_DIR")),
         )
         .unwrap();
         std::fs::create_dir(dir.path().join("src")).unwrap();
         let source_path = dir.path().join("src/lib.rs");
         let mut source = File::create(&source_path).unwrap();
-        generate_serde("test", &mut source, &registry.unwrap()).unwrap();
+        generate_serde_with_config(config, &mut source, registry).unwrap();
+        if !tests.is_empty() {
+            std::fs::create_dir(dir.path().join("tests")).unwrap();
+            std::fs::write(
+                dir.path().join("tests/generated.rs"),
+                tests.replace("$CRATE_DIR", env!("CARGO_MANIFEST_DIR")),
+            )
+            .unwrap();
+        }
         // Use a stable `target` dir to avoid downloading and recompiling crates everytime.
         let target_dir = std::env::current_dir().unwrap().join("../target");
         let status = Command::new("cargo")
             .current_dir(dir.path())
-            .arg("build")
+            .arg(command)
             .arg("--target-dir")
             .arg(target_dir)
             .status()
@@ -473,6 +3225,218 @@ mod test {
         assert!(status.success());
     }
 
+    #[test]
+    fn test_generate_serde_model_from_registry() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let registry = export_schema_to_registry(tf_schema.as_ref().unwrap());
+        let config = CodeGeneratorConfig::new("test".to_owned());
+        assert_generated_code_compiles(&config, &registry.unwrap());
+    }
+
+    #[test]
+    fn test_generate_serde_model_with_options() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let mut config = CodeGeneratorConfig::new("test".to_owned())
+            .with_sensitive_wrapper(true)
+            .with_zeroize_sensitive(true)
+            .with_separate_state(true)
+            .with_object_layout(true)
+            .with_expression_wrapper(true);
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+        assert_generated_code_compiles(&config, &registry.unwrap());
+    }
+
+    #[test]
+    fn test_decode_state_values() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/terraform-provider-schema.json");
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+        assert_generated_code_runs(
+            &config,
+            &registry.unwrap(),
+            r#"
+use testing::*;
+
+#[test]
+fn decode_state_values() {
+    let state: state =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/state_test.json")).unwrap();
+    let resources = &state.values.unwrap().root_module.resources;
+    assert_eq!(resources.len(), 3);
+
+    let remote_state = match resources[0].decode_data() {
+        Some(Ok(data_values::terraform_remote_state(values))) => values,
+        other => panic!("unexpected values {:?}", other),
+    };
+    assert_eq!(remote_state.backend, "local");
+    assert_eq!(remote_state.outputs.unwrap()["vpc"], "vpc-1");
+    assert!(resources[0].decode_resource().is_none());
+
+    let data = match resources[2].decode_resource() {
+        Some(Ok(resource_values::terraform_data(values))) => values,
+        other => panic!("unexpected values {:?}", other),
+    };
+    assert_eq!(data.id, Some("3c950340-dfa1-0c50-59a1-5ca695d61fd7".to_owned()));
+    assert_eq!(data.output, data.input);
+    assert_eq!(resources[2].depends_on, vec!["terraform_data.seed"]);
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_decode_plan_changes() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/terraform-provider-schema.json");
+        // plans decode into the optional state structs, with or without separate state
+        for separate_state in &[false, true] {
+            let mut config =
+                CodeGeneratorConfig::new("test".to_owned()).with_separate_state(*separate_state);
+            let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+            assert_generated_code_runs(&config, &registry.unwrap(), DECODE_PLAN_CHANGES);
+        }
+    }
+
+    const DECODE_PLAN_CHANGES: &str = r#"
+use testing::*;
+
+#[test]
+fn decode_plan_changes() {
+    let plan: plan =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/plan_test.json")).unwrap();
+    let changes = &plan.resource_changes;
+    assert_eq!(changes.len(), 3);
+
+    // `backend` is required, but only known after apply
+    let change = match changes[0].decode_data() {
+        Some(Ok(data_change_values::terraform_remote_state(change))) => change,
+        other => panic!("unexpected change {:?}", other),
+    };
+    assert!(change.before.is_none());
+    let after = change.after.unwrap();
+    assert_eq!(after.backend, None);
+    assert!(after.config.is_some());
+
+    let change = match changes[1].decode_resource() {
+        Some(Ok(resource_change_values::terraform_data(change))) => change,
+        other => panic!("unexpected change {:?}", other),
+    };
+    let after = change.after.unwrap();
+    assert_eq!(after.id, None);
+    assert_eq!(after.input.map(|v| v.0), Some(serde_json::json!("seed")));
+    assert!(changes[1].decode_data().is_none());
+}
+"#;
+
+    #[test]
+    fn test_unmarshall_array_form_with_object_layout() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let mut config = CodeGeneratorConfig::new("test".to_owned()).with_object_layout(true);
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+        assert_generated_code_runs(
+            &config,
+            &registry.unwrap(),
+            r##"
+#![allow(deprecated)]
+use testing::*;
+
+#[test]
+fn unmarshall_array_form() {
+    let res: config =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/resource_test.json")).unwrap();
+    let resource = &res.resource.test_resource_a.unwrap()["test"];
+    assert_eq!(resource.name, "test_resource_a");
+    assert_eq!(resource.client_whitelist.len(), 3);
+
+    let res: config =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/datasource_test.json"))
+            .unwrap();
+    let data = &res.data.test_data_source_b.unwrap()["test"];
+    assert_eq!(data.name, "test_datasource_b");
+
+    let res: config =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/block_type_test.json"))
+            .unwrap();
+    let data = &res.data.test_data_source_a.unwrap()["test"];
+    assert_eq!(data.datasource_a_type[0].filter_type.as_deref(), Some("REGEX"));
+
+    let res: config = serde_json::from_str(
+        r#"{"provider": [{"test_provider": {"api_token": "t", "base_url": "https://a", "org_name": "o"}}, {"test_provider": {"alias": "west", "api_token": "t", "base_url": "https://b", "org_name": "o"}}]}"#,
+    )
+    .unwrap();
+    let providers = res.provider.test_provider.unwrap();
+    assert_eq!(providers.len(), 2);
+    assert_eq!(providers[0].alias, None);
+    assert_eq!(providers[0].base_url.as_deref(), Some("https://a"));
+    assert_eq!(providers[1].alias.as_deref(), Some("west"));
+}
+"##,
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_config_blocks() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+        assert_generated_code_runs(
+            &config,
+            &registry.unwrap(),
+            r#"
+use testing::*;
+
+#[test]
+fn unmarshall_array_form() {
+    let res: config =
+        serde_json::from_str(include_str!("$CRATE_DIR/tests/fixtures/config_blocks_test.json"))
+            .unwrap();
+    let variables = res.variable.unwrap();
+    assert_eq!(variables.len(), 2);
+    assert_eq!(variables["zones"].r#type.as_deref(), Some("list(string)"));
+    assert_eq!(res.output.unwrap()["arn"].sensitive, Some(true));
+    let network = &res.module.unwrap()["network"];
+    assert_eq!(network.source, "./network");
+    assert_eq!(network.inputs["cidr"].as_str(), Some("10.0.0.0/16"));
+
+    let settings = res.terraform.unwrap();
+    let provider = &settings.required_providers.unwrap()["test_provider"];
+    assert_eq!(provider.source.as_deref(), Some("acme/test_provider"));
+    assert!(settings.backend.unwrap().contains_key("local"));
+}
+"#,
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_single_item_list_block() {
+        let tf_schema = read_tf_schema_from_file("./tests/fixtures/test-provider-schema.json");
+        let mut config = CodeGeneratorConfig::new("test".to_owned());
+        let registry = export_schema_with_config(tf_schema.as_ref().unwrap(), &mut config);
+        assert_generated_code_runs(
+            &config,
+            &registry.unwrap(),
+            r##"
+#![allow(deprecated)]
+use testing::*;
+
+#[test]
+fn unmarshall_single_item_list_block() {
+    for network in &[r#"[{"subnet_id": "a"}]"#, r#"{"subnet_id": "a"}"#] {
+        let json = format!(
+            r#"{{"name": "b", "auth": {{"username": "u"}}, "target": [{{"arn": "t"}}], "network": {}}}"#,
+            network
+        );
+        let res: test_resource_b_details = serde_json::from_str(&json).unwrap();
+        assert_eq!(res.network.unwrap().subnet_id, "a");
+    }
+
+    let json = r#"{"name": "b", "auth": {"username": "u"}, "target": [{"arn": "t"}], "network": []}"#;
+    let res: test_resource_b_details = serde_json::from_str(json).unwrap();
+    assert!(res.network.is_none());
+}
+"##,
+        );
+    }
+
     #[test]
     fn test_unmarshall_provider() {
         let res: config =
@@ -566,20 +3530,41 @@ mod test {
             res_a.map(|x| x.name.to_owned()),
             Some("test_datasource_a".to_owned())
         );
-        assert_eq!(res_a.map(|x| x.datasource_a_type.is_none()), Some(false));
+        assert_eq!(res_a.map(|x| x.datasource_a_type.is_empty()), Some(false));
         assert_eq!(
-            res_a.and_then(|x| x.datasource_a_type.as_ref().map(|x| x.is_empty())),
-            Some(false)
+            res_a.and_then(|x| x.datasource_a_type.first().unwrap().filter_type.to_owned()),
+            Some("REGEX".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_unmarshall_object_form() {
+        let res: config =
+            serde_json::from_str(include_str!("../tests/fixtures/object_form_test.json")).unwrap();
+        assert_eq!(res.data.as_ref().map(|x| x.len()), Some(2));
+        let prv = res
+            .provider
+            .as_ref()
+            .and_then(|x| x.first())
+            .and_then(|x| match x {
+                provider_root::test_provider(p) => p.first(),
+            });
+        assert_eq!(
+            prv.map(|x| x.api_token.to_owned()),
+            Some("ABC12345".to_owned())
         );
+        let res_a = res
+            .data
+            .as_ref()
+            .and_then(|x| x.first())
+            .and_then(|x| match x {
+                datasource_root::test_data_source_a(ds1) => ds1.first(),
+                _ => None,
+            })
+            .and_then(|x| x.get("test"))
+            .and_then(|x| x.first());
         assert_eq!(
-            res_a.and_then(|x| x
-                .datasource_a_type
-                .as_ref()
-                .unwrap()
-                .first()
-                .unwrap()
-                .filter_type
-                .to_owned()),
+            res_a.and_then(|x| x.datasource_a_type.first().unwrap().filter_type.to_owned()),
             Some("REGEX".to_owned())
         );
     }
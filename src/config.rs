@@ -9,6 +9,13 @@ pub struct CodeGeneratorConfig {
     pub(crate) module_name: String,
     pub(crate) external_definitions: ExternalDefinitions,
     pub(crate) comments: DocComments,
+    pub(crate) annotations: Annotations,
+    pub(crate) custom_code: CustomCode,
+    pub(crate) sensitive_wrapper: bool,
+    pub(crate) zeroize_sensitive: bool,
+    pub(crate) separate_state: bool,
+    pub(crate) object_layout: bool,
+    pub(crate) expression_wrapper: bool,
 }
 
 /// Track types definitions provided by external modules.
@@ -19,6 +26,18 @@ pub type ExternalDefinitions =
 pub type DocComments =
     std::collections::BTreeMap</* qualified name */ Vec<String>, /* comment */ String>;
 
+/// Track attributes (e.g. `#[deprecated]`) to be attached to particular definitions.
+pub type Annotations = std::collections::BTreeMap<
+    /* qualified name */ Vec<String>,
+    /* attributes */ Vec<String>,
+>;
+
+/// Track custom code to be added after particular definitions (use with care!).
+pub type CustomCode = std::collections::BTreeMap<
+    /* qualified name */ Vec<String>,
+    /* custom code */ String,
+>;
+
 impl CodeGeneratorConfig {
     /// Default config for the given module name.
     pub fn new(module_name: String) -> Self {
@@ -26,6 +45,13 @@ impl CodeGeneratorConfig {
             module_name,
             external_definitions: BTreeMap::new(),
             comments: BTreeMap::new(),
+            annotations: BTreeMap::new(),
+            custom_code: BTreeMap::new(),
+            sensitive_wrapper: false,
+            zeroize_sensitive: false,
+            separate_state: false,
+            object_layout: false,
+            expression_wrapper: false,
         }
     }
 
@@ -44,4 +70,92 @@ impl CodeGeneratorConfig {
         self.comments = comments;
         self
     }
+
+    /// Attributes attached to particular entity.
+    pub fn with_annotations(mut self, annotations: Annotations) -> Self {
+        self.annotations = annotations;
+        self
+    }
+
+    /// Custom code added after particular definitions (e.g. `impl` blocks).
+    pub fn with_custom_code(mut self, code: CustomCode) -> Self {
+        self.custom_code = code;
+        self
+    }
+
+    /// Whether attributes marked as sensitive are wrapped in `runtime::Sensitive`, which
+    /// redacts their `Debug` output.
+    pub fn with_sensitive_wrapper(mut self, sensitive_wrapper: bool) -> Self {
+        self.sensitive_wrapper = sensitive_wrapper;
+        self
+    }
+
+    /// Whether sensitive strings, numbers and booleans (or collections of them) are wrapped in
+    /// `runtime::Secret` instead, which also wipes them on drop. Generated code then requires
+    /// the `zeroize` feature.
+    pub fn with_zeroize_sensitive(mut self, zeroize_sensitive: bool) -> Self {
+        self.zeroize_sensitive = zeroize_sensitive;
+        self
+    }
+
+    /// Whether resources and data sources are generated as a `{name}_config` struct holding
+    /// the arguments users may set, instead of a `{name}_details` struct holding arguments and
+    /// attributes. Either way, a `{name}_state` struct holds every attribute (including
+    /// computed ones, all of them optional so plans with values only known after apply decode
+    /// too); with separate state, state documents decode into it rather than into the details.
+    pub fn with_separate_state(mut self, separate_state: bool) -> Self {
+        self.separate_state = separate_state;
+        self
+    }
+
+    /// Whether config roots are generated as structs with a field per type, holding items
+    /// keyed by name (e.g. `config.resource.aws_instance["web"]`), instead of enums matching
+    /// the array form of Terraform JSON.
+    pub fn with_object_layout(mut self, object_layout: bool) -> Self {
+        self.object_layout = object_layout;
+        self
+    }
+
+    /// Whether attributes of any type other than string (and elements of collections) are
+    /// wrapped in `runtime::Expr`, so they may also hold Terraform expressions (e.g.
+    /// `"${var.replicas}"`). Meta-arguments and settings (e.g. `depends_on` or the arguments of
+    /// `lifecycle` and `variable` blocks) are left unwrapped, as Terraform requires literals.
+    pub fn with_expression_wrapper(mut self, expression_wrapper: bool) -> Self {
+        self.expression_wrapper = expression_wrapper;
+        self
+    }
+
+    /// Qualified name of a definition (or field) within the generated module.
+    pub(crate) fn qualified_name(&self, path: &[&str]) -> Vec<String> {
+        self.module_name
+            .split('.')
+            .chain(path.iter().cloned())
+            .map(String::from)
+            .collect()
+    }
+
+    /// Attach a comment to the definition (or field) at the given path.
+    pub(crate) fn add_comment(&mut self, path: &[&str], comment: &str) {
+        let comment = comment.trim();
+        if !comment.is_empty() {
+            let name = self.qualified_name(path);
+            self.comments.insert(name, format!("{}\n", comment));
+        }
+    }
+
+    /// Attach an attribute to the definition (or field) at the given path.
+    pub(crate) fn add_annotation(&mut self, path: &[&str], annotation: String) {
+        let name = self.qualified_name(path);
+        self.annotations.entry(name).or_default().push(annotation);
+    }
+
+    /// Append custom code after the definition at the given path.
+    pub(crate) fn add_custom_code(&mut self, path: &[&str], code: &str) {
+        let name = self.qualified_name(path);
+        let entry = self.custom_code.entry(name).or_default();
+        if !entry.is_empty() {
+            entry.push_str("\n\n");
+        }
+        entry.push_str(code.trim());
+    }
 }
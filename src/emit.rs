@@ -17,6 +17,24 @@ use std::io::{Result, Write};
 pub type QualifiedName = (Option<String>, String);
 pub type Registry = BTreeMap<QualifiedName, ContainerFormat>;
 
+/// Definitions of the `tfschema_bindgen::runtime` module imported by generated code.
+const RUNTIME_DEFINITIONS: [&str; 9] = [
+    "Change",
+    "Count",
+    "Dynamic",
+    "Expr",
+    "ForEach",
+    "IgnoreChanges",
+    "ProviderRef",
+    "Sensitive",
+    "Set",
+];
+
+/// Rust type of a format, as it is written in generated code (without boxing).
+pub(crate) fn quote_type(format: &Format) -> String {
+    RustEmitter::<Vec<u8>>::quote_type(format, None)
+}
+
 /// Main configuration object for code-generation in Rust.
 pub struct CodeGenerator<'a> {
     /// Language-independent configuration.
@@ -80,13 +98,20 @@ impl<'a> CodeGenerator<'a> {
         out: &mut dyn Write,
         registry: &Registry,
     ) -> std::result::Result<(), Box<dyn std::error::Error>> {
-        let external_names: BTreeSet<String> = self
+        let mut external_names: BTreeSet<String> = self
             .config
             .external_definitions
             .values()
-            .cloned()
             .flatten()
+            .cloned()
             .collect();
+        // Terraform schemas describe trees of blocks: no definition can recursively
+        // contain itself, so none of the registry entries needs to be boxed.
+        external_names.extend(registry.keys().map(|(ns, name)| match ns {
+            Some(ns) => format!("{}_{}", ns, name),
+            None => name.to_owned(),
+        }));
+        external_names.extend(RUNTIME_DEFINITIONS.iter().map(|x| x.to_string()));
 
         let known_sizes = external_names
             .iter()
@@ -129,16 +154,36 @@ where
         Ok(())
     }
 
+    fn output_annotations(&mut self, name: &str) -> std::io::Result<()> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        if let Some(annotations) = self.generator.config.annotations.get(&path) {
+            for annotation in annotations {
+                writeln!(self.out, "{}", annotation)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn output_custom_code(&mut self, name: &str) -> std::io::Result<()> {
+        let mut path = self.current_namespace.clone();
+        path.push(name.to_string());
+        if let Some(code) = self.generator.config.custom_code.get(&path) {
+            writeln!(self.out, "{}\n", code)?;
+        }
+        Ok(())
+    }
+
     fn output_preamble(&mut self) -> Result<()> {
         let external_names = self
             .generator
             .config
             .external_definitions
             .values()
-            .cloned()
             .flatten()
+            .cloned()
             .collect::<HashSet<_>>();
-        writeln!(self.out, "#![allow(unused_imports, non_snake_case, non_camel_case_types, non_upper_case_globals)]")?;
+        writeln!(self.out, "#![allow(unused_imports, non_snake_case, non_camel_case_types, non_upper_case_globals, deprecated)]")?;
         if !external_names.contains("Map") {
             writeln!(self.out, "use std::collections::BTreeMap as Map;")?;
         }
@@ -146,6 +191,22 @@ where
         if !external_names.contains("Bytes") {
             writeln!(self.out, "use serde_bytes::ByteBuf as Bytes;")?;
         }
+        let mut runtime_names = RUNTIME_DEFINITIONS
+            .iter()
+            .filter(|x| !external_names.contains(**x))
+            .cloned()
+            .collect::<Vec<_>>();
+        // only available with the `zeroize` feature of the runtime
+        if self.generator.config.zeroize_sensitive && !external_names.contains("Secret") {
+            runtime_names.push("Secret");
+        }
+        if !runtime_names.is_empty() {
+            writeln!(
+                self.out,
+                "use tfschema_bindgen::runtime::{{{}}};",
+                runtime_names.join(", ")
+            )?;
+        }
         for (module, definitions) in &self.generator.config.external_definitions {
             // Skip the empty module name.
             if !module.is_empty() {
@@ -174,7 +235,15 @@ where
             )?,
             Seq(_) => writeln!(
                 self.out,
-                "#[serde(skip_serializing_if = \"Vec::is_empty\")]"
+                "#[serde(default, skip_serializing_if = \"Vec::is_empty\")]"
+            )?,
+            Map { .. } => writeln!(
+                self.out,
+                "#[serde(default, skip_serializing_if = \"Map::is_empty\")]"
+            )?,
+            TypeName(x) if x.starts_with("Set<") => writeln!(
+                self.out,
+                "#[serde(default, skip_serializing_if = \"Set::is_empty\")]"
             )?,
             _ => (),
         }
@@ -187,7 +256,7 @@ where
         match format {
             TypeName(x) => {
                 if let Some(set) = known_sizes {
-                    if !set.contains(x.as_str()) && !x.as_str().starts_with("Vec") {
+                    if !set.contains(x.as_str()) && !x.as_str().contains('<') {
                         return format!("Box<{}>", x);
                     }
                 }
@@ -244,6 +313,7 @@ where
         };
         for field in fields {
             self.output_comment(&field.name)?;
+            self.output_annotations(&field.name)?;
             self.output_field_annotation(&field.value)?;
             writeln!(
                 self.out,
@@ -258,6 +328,7 @@ where
 
     fn output_variant(&mut self, base: &str, name: &str, variant: &VariantFormat) -> Result<()> {
         self.output_comment(name)?;
+        self.output_annotations(name)?;
         use VariantFormat::*;
         match variant {
             Unit => writeln!(self.out, "{},", name),
@@ -304,7 +375,12 @@ where
         name: &str,
         format: &ContainerFormat,
     ) -> Result<()> {
-        self.output_comment(name)?;
+        // Namespaced definitions are emitted (and documented) under their qualified name.
+        let qualified_name = namespace
+            .as_ref()
+            .map_or_else(|| name.to_string(), |ns| format!("{}_{}", ns, name));
+        self.output_comment(&qualified_name)?;
+        self.output_annotations(&qualified_name)?;
         let mut derive_macros = self.generator.derive_macros.clone();
         derive_macros.push("Serialize".to_string());
         derive_macros.push("Deserialize".to_string());
@@ -340,22 +416,20 @@ where
                 Self::quote_types(formats, Some(&self.known_sizes))
             ),
             Struct(fields) => {
-                let mut struct_name = name.to_string();
                 prefix.clear();
                 derive_macros.push("Default".to_string());
                 prefix.push_str(&format!("#[derive({})]\n", derive_macros.join(", ")));
 
-                if let Some(ns) = namespace {
+                if namespace.is_some() {
                     prefix.push_str(&format!("#[serde(rename = \"{}\")]\n", name));
-                    struct_name = format!("{}_{}", ns, name)
                 }
 
                 if self.generator.track_visibility {
                     prefix.push_str("pub ");
                 }
 
-                writeln!(self.out, "{}struct {} {{", prefix, struct_name)?;
-                self.current_namespace.push(name.to_string());
+                writeln!(self.out, "{}struct {} {{", prefix, qualified_name)?;
+                self.current_namespace.push(qualified_name.clone());
                 self.out.indent();
                 self.output_fields(&[name], fields)?;
                 self.out.unindent();
@@ -375,6 +449,7 @@ where
                 self.current_namespace.pop();
                 writeln!(self.out, "}}\n")
             }
-        }
+        }?;
+        self.output_custom_code(&qualified_name)
     }
 }
@@ -7,8 +7,9 @@
 use std::path::PathBuf;
 use structopt::StructOpt;
 use tfschema_bindgen::binding::{
-    export_schema_to_registry, generate_serde, read_tf_schema_from_file,
+    export_schema_with_config, generate_serde_with_config, read_tf_schema_from_file,
 };
+use tfschema_bindgen::config::CodeGeneratorConfig;
 
 #[derive(Debug, StructOpt)]
 #[structopt(
@@ -19,6 +20,28 @@ struct Options {
     /// Path to the JSON-encoded terraform schema.
     #[structopt(parse(from_os_str))]
     input: Option<PathBuf>,
+
+    /// Wrap sensitive attributes so that their `Debug` output is redacted.
+    #[structopt(long)]
+    sensitive_wrapper: bool,
+
+    /// Also wipe sensitive values on drop (generated code requires the `zeroize` feature).
+    #[structopt(long, requires = "sensitive-wrapper")]
+    zeroize_sensitive: bool,
+
+    /// Generate `*_config` (arguments) structs for resources and data sources instead of
+    /// `*_details`, and decode state into the `*_state` (all attributes) structs.
+    #[structopt(long)]
+    separate_state: bool,
+
+    /// Generate config roots as structs keyed by type and name (e.g.
+    /// `config.resource.aws_instance["web"]`).
+    #[structopt(long)]
+    object_layout: bool,
+
+    /// Wrap non-string attributes so they may also hold expressions (e.g. `"${var.replicas}"`).
+    #[structopt(long)]
+    expression_wrapper: bool,
 }
 
 fn main() {
@@ -27,10 +50,16 @@ fn main() {
         .input
         .as_ref()
         .map(|input| read_tf_schema_from_file(input).unwrap());
-    let registry = export_schema_to_registry(schema_deserialized.as_ref().unwrap())
+    let mut config = CodeGeneratorConfig::new("default".to_string())
+        .with_sensitive_wrapper(options.sensitive_wrapper)
+        .with_zeroize_sensitive(options.zeroize_sensitive)
+        .with_separate_state(options.separate_state)
+        .with_object_layout(options.object_layout)
+        .with_expression_wrapper(options.expression_wrapper);
+    let registry = export_schema_with_config(schema_deserialized.as_ref().unwrap(), &mut config)
         .expect("Error exporting terraform provider schema to serde-reflection");
     let stdout = std::io::stdout();
     let mut out = stdout.lock();
 
-    generate_serde("default", &mut out, &registry).expect("Error generating serde model")
+    generate_serde_with_config(&config, &mut out, &registry).expect("Error generating serde model")
 }
@@ -46,84 +46,122 @@
 //! cargo run --bin tfbindgen -- test.json > test.rs
 //! ```
 //!
-//! The following is a Rust example snippet comprising the previously generated bindings and a main function building on these in order
-//! deserialize a configuration descriptor adhering to our Terraform schema:
-//!
+//! Besides `serde` (with its `derive` feature) and `serde_bytes`, generated code depends on this
+//! crate at runtime: it builds on definitions of its `runtime` module, which also hosts the functions
+//! accepting both the object and array forms of Terraform JSON. The generator itself sits behind the
+//! default `codegen` feature, which the runtime doesn't need:
+//!
+//! ```toml
+//! [dependencies]
+//! serde = { version = "1.0", features = ["derive"] }
+//! serde_bytes = "0.11"
+//! tfschema-bindgen = { version = "0.1", default-features = false }
 //! ```
-//! #![allow(unused_imports, non_snake_case, non_camel_case_types, non_upper_case_globals)]
+//!
+//! Bindings generated with `--zeroize-sensitive` further require the `zeroize` feature
+//! (`features = ["zeroize"]`).
+//!
+//! The following is an excerpt of the generated bindings. The module starts by importing the runtime
+//! definitions it builds on: `Change`, `Count`, `Dynamic`, `Expr`, `ForEach`, `IgnoreChanges`,
+//! `ProviderRef`, `Sensitive` and `Set` (along with `Secret` when generated with `--zeroize-sensitive`).
+//! Besides the types of the schema, it holds the definitions of other Terraform JSON blocks
+//! (e.g. `variable` or `terraform`), and models of the state and plan documents printed by
+//! `terraform show -json`:
+//!
+//! ```rust,ignore
+//! #![allow(unused_imports, non_snake_case, non_camel_case_types, non_upper_case_globals, deprecated)]
 //! use std::collections::BTreeMap as Map;
 //! use serde::{Serialize, Deserialize};
 //! use serde_bytes::ByteBuf as Bytes;
+//! use tfschema_bindgen::runtime::{Change, Count, Dynamic, Expr, ForEach, IgnoreChanges, ProviderRef, Sensitive, Set};
 //!
 //! #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
 //! pub struct config {
+//!     #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_roots")]
 //!     #[serde(skip_serializing_if = "Option::is_none")]
 //!     pub data: Option<Vec<data_root>>,
+//!     #[serde(default, deserialize_with = "tfschema_bindgen::runtime::deserialize_roots")]
 //!     #[serde(skip_serializing_if = "Option::is_none")]
 //!     pub provider: Option<Vec<provider_root>>,
-//!     #[serde(skip_serializing_if = "Option::is_none")]
-//!     pub resource: Option<Vec<resource_root>>,
-//! }
-//!
-//! #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-//! pub enum data_root {
-//! }
-//!
+//! // ...
 //! #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 //! pub enum provider_root {
+//!     #[serde(deserialize_with = "tfschema_bindgen::runtime::deserialize_blocks")]
 //!     test_provider(Vec<test_provider_details>),
 //! }
-//!
-//! #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
-//! pub enum resource_root {
-//! }
-//!
+//! // ...
 //! #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize, Default)]
 //! pub struct test_provider_details {
+//!
+//!     /// Name of an additional configuration of the provider, referenced as `{provider}.{alias}`.
+//!     #[serde(skip_serializing_if = "Option::is_none")]
+//!     pub alias: Option<String>,
+//!
+//!     /// The url.
 //!     #[serde(skip_serializing_if = "Option::is_none")]
 //!     pub base_url: Option<String>,
 //! }
+//! ```
 //!
+//! The following main function builds on these bindings in order to deserialize a configuration
+//! descriptor adhering to our Terraform schema:
+//!
+//! ```rust,ignore
 //! const TF_JSON_CONFIG: &str = r#"{
-//!    "provider": [
-//!      {
-//!        "test_provider": [
-//!          {
-//!            "base_url": "https://acme.com/foo"
-//!          }
-//!        ]
-//!      }
-//!    ]
-//! }"#;
+//!     "provider": [
+//!       {
+//!         "test_provider": [
+//!           {
+//!             "base_url": "https://acme.com/foo"
+//!           }
+//!         ]
+//!       }
+//!     ]
+//!   }"#;
 //!
 //! fn main() -> Result<(), std::io::Error> {
-//!    let res: config = serde_json::from_str(TF_JSON_CONFIG).unwrap();
-//!
-//!    assert_eq!(res.provider.as_ref().map(|x| x.is_empty()), Some(false));
-//!    assert_eq!(
-//!        res.provider.as_ref().map(|x| x.get(0).is_none()),
-//!        Some(false)
-//!    );
-//!    let prv = res
-//!        .provider
-//!        .as_ref()
-//!        .and_then(|x| x.get(0))
-//!        .and_then(|x| match x {
-//!            provider_root::test_provider(p) => p.get(0),
-//!        });
-//!    assert_eq!(prv.is_none(), false);
-//!    assert_eq!(
-//!        prv.and_then(|x| x.base_url.to_owned()),
-//!        Some("https://acme.com/foo".to_owned())
-//!    );
-//!    print!("success!\n");
-//!    Ok(())
+//!     let res: config = serde_json::from_str(TF_JSON_CONFIG).unwrap();
+//!
+//!     assert_eq!(res.provider.as_ref().map(|x| x.is_empty()), Some(false));
+//!     let prv = res
+//!         .provider
+//!         .as_ref()
+//!         .and_then(|x| x.first())
+//!         .and_then(|x| match x {
+//!             provider_root::test_provider(p) => p.first(),
+//!         });
+//!     assert!(prv.is_some());
+//!     assert_eq!(
+//!         prv.and_then(|x| x.base_url.to_owned()),
+//!         Some("https://acme.com/foo".to_owned())
+//!     );
+//!     println!("success!");
+//!     Ok(())
 //! }
 //! ```
+//!
+//! ## Options
+//!
+//! The shape of generated bindings may be tuned with the following flags of `tfbindgen` (or the
+//! matching `with_*` methods of `config::CodeGeneratorConfig`):
+//!
+//! * `--sensitive-wrapper`: wrap sensitive attributes in `Sensitive`, which redacts their `Debug` output.
+//! * `--zeroize-sensitive`: wrap sensitive strings, numbers and booleans in `Secret` instead, which
+//!   also wipes them on drop (requires `--sensitive-wrapper`, and the `zeroize` feature of this crate
+//!   in the crate using the bindings).
+//! * `--separate-state`: generate a `*_config` struct (arguments) per resource and data source,
+//!   instead of a `*_details` struct, and decode state into the `*_state` struct (all attributes,
+//!   which plans always decode into).
+//! * `--object-layout`: generate config roots as structs keyed by type and name (e.g.
+//!   `config.resource.aws_instance["web"]`), instead of enums matching the array form of Terraform JSON.
+//! * `--expression-wrapper`: wrap attributes of any type other than string in `Expr`, so they may
+//!   also hold Terraform expressions (e.g. `"${var.replicas}"`). Meta-arguments and settings, which
+//!   Terraform requires to be literals, are left unwrapped.
+//!
 //! ## Quickstart Example
 //!
-//! In addition to a Rust library and generation tool, this crate provides the above example which
-//! can be executed using the following command:
+//! In addition to a Rust library and generation tool, this crate provides the above example, along
+//! with the complete bindings it builds on, which can be executed using the following command:
 //!
 //! ```bash
 //! cargo run --example quickstart
@@ -165,14 +203,20 @@
 //!
 
 // registry creation
+#[cfg(feature = "codegen")]
 pub mod binding;
 
 // code generator
+#[cfg(feature = "codegen")]
 pub mod emit;
 
 // configuraiton support for code generation
+#[cfg(feature = "codegen")]
 pub mod config;
 
+// support types for generated code
+pub mod runtime;
+
 /// Utility functions to help testing code generators.
 #[doc(hidden)]
 pub mod test_utils;
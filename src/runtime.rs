@@ -0,0 +1,929 @@
+//!
+//! Support types referenced by generated bindings.
+//!
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::iter::FromIterator;
+use std::ops::{Deref, DerefMut};
+use std::str::FromStr;
+
+/// Untyped value of attributes declared with the `dynamic` pseudo-type.
+///
+/// Generated definitions derive `PartialOrd`, which `serde_json::Value` lacks: values
+/// are ordered by their canonical JSON text instead.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Dynamic(pub Value);
+
+/// Error raised when a value does not match the definition it is decoded into.
+pub type DecodeError = serde_json::Error;
+
+impl Dynamic {
+    /// Decodes the value into a generated definition.
+    pub fn decode<T: DeserializeOwned>(&self) -> Result<T, DecodeError> {
+        T::deserialize(&self.0)
+    }
+}
+
+impl PartialOrd for Dynamic {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.0.to_string().partial_cmp(&other.0.to_string())
+    }
+}
+
+impl Deref for Dynamic {
+    type Target = Value;
+
+    fn deref(&self) -> &Value {
+        &self.0
+    }
+}
+
+impl DerefMut for Dynamic {
+    fn deref_mut(&mut self) -> &mut Value {
+        &mut self.0
+    }
+}
+
+impl From<Value> for Dynamic {
+    fn from(value: Value) -> Self {
+        Dynamic(value)
+    }
+}
+
+/// Values of a resource (or data source) before and after a planned change.
+///
+/// `before` is `None` when the item is created, `after` when it is destroyed.
+#[derive(Clone, Debug, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+pub struct Change<T> {
+    pub before: Option<T>,
+    pub after: Option<T>,
+}
+
+/// Value of the `count` meta-argument: a number, or an expression evaluating to one.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Count {
+    Number(i64),
+    Expr(String),
+}
+
+impl From<i64> for Count {
+    fn from(count: i64) -> Self {
+        Count::Number(count)
+    }
+}
+
+/// Value of the `for_each` meta-argument: a set of strings, a map, or an expression
+/// evaluating to one of those.
+///
+/// Terraform reads a JSON array as a tuple, which `for_each` rejects: a set is serialized as a
+/// `toset` expression (e.g. `"${toset([\"a\", \"b\"])}"`), though it is still deserialized
+/// from an array.
+#[derive(Clone, Debug, PartialEq, PartialOrd, Deserialize)]
+#[serde(untagged)]
+pub enum ForEach {
+    Set(Set<String>),
+    Map(BTreeMap<String, Dynamic>),
+    Expr(String),
+}
+
+impl Serialize for ForEach {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ForEach::Set(set) => {
+                // strings of expressions are templates too
+                let items = set
+                    .0
+                    .iter()
+                    .map(|item| Value::String(escape_template(item)).to_string())
+                    .collect::<Vec<_>>();
+                serializer.serialize_str(&format!("${{toset([{}])}}", items.join(", ")))
+            }
+            ForEach::Map(map) => map.serialize(serializer),
+            ForEach::Expr(expression) => serializer.serialize_str(expression),
+        }
+    }
+}
+
+/// Value of the `ignore_changes` lifecycle argument: the `all` keyword, or references to the
+/// attributes whose changes are ignored.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum IgnoreChanges {
+    All,
+    Attributes(Vec<String>),
+}
+
+impl Serialize for IgnoreChanges {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            IgnoreChanges::All => serializer.serialize_str("all"),
+            IgnoreChanges::Attributes(attributes) => attributes.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for IgnoreChanges {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match Value::deserialize(deserializer)? {
+            Value::String(s) if s == "all" => Ok(IgnoreChanges::All),
+            value => Vec::deserialize(value)
+                .map(IgnoreChanges::Attributes)
+                .map_err(|_| de::Error::custom("expected `all` or a list of attributes")),
+        }
+    }
+}
+
+/// Value of an attribute written either as a typed literal or as a Terraform expression
+/// (e.g. `"${var.replicas}"`).
+///
+/// Only a value written as a string is read as an expression: strings nested in a literal
+/// are templates of their own and are left untouched (elements of collections may be wrapped
+/// in `Expr` too, so they may hold expressions). A literal string is escaped when serialized
+/// (`${` as `$${`, `%{` as `%%{`) so Terraform does not read it as an interpolation, and
+/// unescaped when deserialized.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub enum Expr<T> {
+    Literal(T),
+    Expr(String),
+}
+
+impl<T> Expr<T> {
+    /// Expression evaluated by Terraform, without the enclosing `${...}`.
+    pub fn interpolate(expression: &str) -> Self {
+        Expr::Expr(format!("${{{}}}", expression))
+    }
+
+    /// The literal value, if not an expression.
+    pub fn literal(&self) -> Option<&T> {
+        match self {
+            Expr::Literal(value) => Some(value),
+            Expr::Expr(_) => None,
+        }
+    }
+}
+
+impl<T: Default> Default for Expr<T> {
+    fn default() -> Self {
+        Expr::Literal(T::default())
+    }
+}
+
+impl<T> From<T> for Expr<T> {
+    fn from(value: T) -> Self {
+        Expr::Literal(value)
+    }
+}
+
+impl From<Expr<String>> for String {
+    /// The literal string, or the expression as a template (e.g. `"${aws_instance.web.id}"`).
+    fn from(value: Expr<String>) -> Self {
+        match value {
+            Expr::Literal(s) | Expr::Expr(s) => s,
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for Expr<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Expr::Literal(value) => {
+                match serde_json::to_value(value).map_err(serde::ser::Error::custom)? {
+                    Value::String(s) => serializer.serialize_str(&escape_template(&s)),
+                    value => value.serialize(serializer),
+                }
+            }
+            Expr::Expr(expression) => serializer.serialize_str(expression),
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Expr<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let literal = match Value::deserialize(deserializer)? {
+            Value::String(s) if is_template(&s) => return Ok(Expr::Expr(s)),
+            Value::String(s) => Value::String(unescape_template(&s)),
+            value => value,
+        };
+        T::deserialize(literal)
+            .map(Expr::Literal)
+            .map_err(de::Error::custom)
+    }
+}
+
+/// Whether a string holds an interpolation (`${...}`) or a directive (`%{...}`).
+fn is_template(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1], bytes.get(i + 2)) {
+            (b'$', b'$', Some(b'{')) | (b'%', b'%', Some(b'{')) => i += 3,
+            (b'$', b'{', _) | (b'%', b'{', _) => return true,
+            _ => i += 1,
+        }
+    }
+    false
+}
+
+fn escape_template(s: &str) -> String {
+    s.replace("${", "$${").replace("%{", "%%{")
+}
+
+fn unescape_template(s: &str) -> String {
+    s.replace("$${", "${").replace("%%{", "%{")
+}
+
+/// Kind of a Terraform schema item.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceKind {
+    Provider,
+    Resource,
+    DataSource,
+}
+
+/// Flags of an attribute, as declared by the provider schema.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AttributeSchema {
+    pub name: &'static str,
+    pub required: bool,
+    pub optional: bool,
+    pub computed: bool,
+    pub sensitive: bool,
+    pub deprecated: bool,
+}
+
+/// Static schema metadata of a provider, resource or data source, implemented by generated
+/// definitions.
+pub trait Resource {
+    /// Type name (e.g. `aws_instance`), or the provider name for providers.
+    const TYPE_NAME: &'static str;
+    const KIND: ResourceKind;
+    /// Name of the provider declaring the item.
+    const PROVIDER: &'static str;
+    const SCHEMA_VERSION: i64;
+    /// Attributes of the item, ordered by name (nested blocks are left out).
+    const ATTRIBUTES: &'static [AttributeSchema];
+
+    /// Flags of the attribute of the given name.
+    fn attribute(name: &str) -> Option<&'static AttributeSchema> {
+        Self::ATTRIBUTES.iter().find(|a| a.name == name)
+    }
+}
+
+/// Reference to a provider configuration, serialized as `name` or as `name.alias` for
+/// additional configurations (e.g. `aws.west`).
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProviderRef {
+    pub name: String,
+    pub alias: Option<String>,
+}
+
+impl ProviderRef {
+    /// Reference to the default configuration of a provider.
+    pub fn new(name: impl Into<String>) -> Self {
+        ProviderRef {
+            name: name.into(),
+            alias: None,
+        }
+    }
+
+    /// Reference to an additional configuration of a provider.
+    pub fn aliased(name: impl Into<String>, alias: impl Into<String>) -> Self {
+        ProviderRef {
+            name: name.into(),
+            alias: Some(alias.into()),
+        }
+    }
+}
+
+impl fmt::Display for ProviderRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.alias {
+            Some(alias) => write!(f, "{}.{}", self.name, alias),
+            None => f.write_str(&self.name),
+        }
+    }
+}
+
+impl FromStr for ProviderRef {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s.find('.') {
+            Some(pos) => ProviderRef::aliased(&s[..pos], &s[pos + 1..]),
+            None => ProviderRef::new(s),
+        })
+    }
+}
+
+impl Serialize for ProviderRef {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ProviderRef {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let reference = String::deserialize(deserializer)?;
+        Ok(reference.parse().unwrap_or_default())
+    }
+}
+
+/// Nested blocks declared with the `set` nesting mode.
+///
+/// Duplicates are skipped on insertion. Unlike `BTreeSet` this only requires items to be
+/// comparable for equality, as generated definitions are; items keep their insertion order.
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+pub struct Set<T>(Vec<T>);
+
+impl<T: PartialEq> Set<T> {
+    /// Creates an empty set.
+    pub fn new() -> Self {
+        Set(Vec::new())
+    }
+
+    /// Adds a value to the set, returning whether it was not present yet.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.0.contains(&value) {
+            return false;
+        }
+        self.0.push(value);
+        true
+    }
+
+    /// Number of items in the set.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Whether the set holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<T> Default for Set<T> {
+    fn default() -> Self {
+        Set(Vec::new())
+    }
+}
+
+impl<T> Deref for Set<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.0
+    }
+}
+
+impl<T: PartialEq> FromIterator<T> for Set<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut set = Set::new();
+        for value in iter {
+            set.insert(value);
+        }
+        set
+    }
+}
+
+impl<T> IntoIterator for Set<T> {
+    type Item = T;
+    type IntoIter = std::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a Set<T> {
+    type Item = &'a T;
+    type IntoIter = std::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: Serialize> Serialize for Set<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de, T: Deserialize<'de> + PartialEq> Deserialize<'de> for Set<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<T>::deserialize(deserializer).map(|items| items.into_iter().collect())
+    }
+}
+
+/// Value of an attribute marked as sensitive by the provider.
+///
+/// (De)serializes as the wrapped value, but is printed as `<sensitive>` by `Debug` so it does
+/// not leak into logs.
+#[derive(Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Sensitive<T>(pub T);
+
+impl<T> Sensitive<T> {
+    /// Unwraps the sensitive value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> fmt::Debug for Sensitive<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<sensitive>")
+    }
+}
+
+impl<T> Deref for Sensitive<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Sensitive<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Sensitive<T> {
+    fn from(value: T) -> Self {
+        Sensitive(value)
+    }
+}
+
+/// Same as [`Sensitive`], except the value is also wiped from memory when dropped.
+///
+/// Only available with the `zeroize` feature, for values of types implementing `Zeroize`.
+#[cfg(feature = "zeroize")]
+#[derive(Clone, Default, PartialEq, PartialOrd, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Secret<T: zeroize::Zeroize>(pub T);
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<sensitive>")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> Deref for Secret<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> DerefMut for Secret<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> From<T> for Secret<T> {
+    fn from(value: T) -> Self {
+        Secret(value)
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T: zeroize::Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+/// Items of a value written either as an array or as a single item (`null` being no item).
+fn into_items(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(items) => items,
+        Value::Null => Vec::new(),
+        value => vec![value],
+    }
+}
+
+/// Item of a value written either as a single item or as an array holding at most one item.
+fn into_single(value: Value) -> Value {
+    match value {
+        Value::Array(mut items) if items.len() <= 1 => items.pop().unwrap_or(Value::Null),
+        value => value,
+    }
+}
+
+/// Deserializes a single block, also accepting it wrapped in an array (e.g. `[{...}]`).
+pub fn deserialize_block<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = into_single(Value::deserialize(deserializer)?);
+    T::deserialize(value).map_err(de::Error::custom)
+}
+
+/// Deserializes a collection of blocks, written either as an array of objects or as a single
+/// object.
+pub fn deserialize_blocks<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: DeserializeOwned,
+{
+    let items = into_items(Value::deserialize(deserializer)?);
+    C::deserialize(Value::Array(items)).map_err(de::Error::custom)
+}
+
+/// Deserializes labeled blocks (e.g. resources named after their label), written either in
+/// the object form `{"web": {...}}` or in the array form `[{"web": [{...}]}]`.
+pub fn deserialize_labeled_blocks<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: DeserializeOwned,
+{
+    let items = into_items(Value::deserialize(deserializer)?)
+        .into_iter()
+        .map(|item| match item {
+            Value::Object(labels) => labels
+                .into_iter()
+                .map(|(label, blocks)| (label, Value::Array(into_items(blocks))))
+                .collect(),
+            item => item,
+        })
+        .collect();
+    C::deserialize(Value::Array(items)).map_err(de::Error::custom)
+}
+
+/// Deserializes labeled blocks keyed by their label, each written either as an object or as
+/// an array holding it (e.g. `{"web": {...}}` or `{"web": [{...}]}`). Also accepts the array
+/// form where labels are spread across several objects (e.g. `[{"web": [{...}]}]`), which are
+/// merged.
+pub fn deserialize_labels<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: DeserializeOwned,
+{
+    let value = match Value::deserialize(deserializer)? {
+        Value::Array(items) => {
+            let mut labels = Map::new();
+            for item in items {
+                match item {
+                    Value::Object(entries) => labels.extend(entries),
+                    _ => return Err(de::Error::custom("expected an array of objects")),
+                }
+            }
+            Value::Object(labels)
+        }
+        value => value,
+    };
+    let value = match value {
+        Value::Object(labels) => labels
+            .into_iter()
+            .map(|(label, block)| (label, into_single(block)))
+            .collect(),
+        value => value,
+    };
+    C::deserialize(value).map_err(de::Error::custom)
+}
+
+/// Deserializes an object keyed by type, also accepting the array form where its keys are
+/// spread across several objects (e.g. `[{"aws_instance": {...}}, {"aws_vpc": {...}}]`).
+/// Values of the same type found in several objects are concatenated, each staying its own
+/// item: fields of labeled blocks (see `deserialize_labels`) merge them by label, while blocks
+/// without labels (e.g. several configurations of a provider) are kept apart.
+pub fn deserialize_object<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = match Value::deserialize(deserializer)? {
+        Value::Array(items) => {
+            let mut merged = Map::new();
+            for item in items {
+                let entries = match item {
+                    Value::Object(entries) => entries,
+                    _ => return Err(de::Error::custom("expected an array of objects")),
+                };
+                for (name, value) in entries {
+                    match (merged.remove(&name), value) {
+                        (Some(previous), value) => {
+                            let mut items = into_items(previous);
+                            items.extend(into_items(value));
+                            merged.insert(name, Value::Array(items));
+                        }
+                        (None, value) => {
+                            merged.insert(name, value);
+                        }
+                    }
+                }
+            }
+            Value::Object(merged)
+        }
+        value => value,
+    };
+    T::deserialize(value).map_err(de::Error::custom)
+}
+
+/// Items of a value written either as an array of objects or as an object, split into one
+/// object per key (e.g. `{"a": 1, "b": 2}` into `{"a": 1}` and `{"b": 2}`).
+fn split_items(value: Value) -> Vec<Value> {
+    into_items(value)
+        .into_iter()
+        .flat_map(|item| match item {
+            Value::Object(entries) => entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut item = Map::new();
+                    item.insert(key, value);
+                    Value::Object(item)
+                })
+                .collect(),
+            item => vec![item],
+        })
+        .collect()
+}
+
+/// Deserializes blocks tagged by their type (e.g. provisioners), written either as an object
+/// with one key per type or as an array of such objects, which keeps them ordered.
+pub fn deserialize_tagged_blocks<'de, D, C>(deserializer: D) -> Result<C, D::Error>
+where
+    D: Deserializer<'de>,
+    C: DeserializeOwned,
+{
+    let items = split_items(Value::deserialize(deserializer)?);
+    C::deserialize(Value::Array(items)).map_err(de::Error::custom)
+}
+
+/// Deserializes the items of a root (e.g. `resource`), written either as an object with one
+/// key per type or as an array of such objects. Items are split into one object per type.
+pub fn deserialize_roots<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = Value::deserialize(deserializer)?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    Vec::<T>::deserialize(Value::Array(split_items(value)))
+        .map(Some)
+        .map_err(de::Error::custom)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_is_transparent() {
+        let manifest = serde_json::json!({"kind": "ConfigMap", "data": {"a": 1}});
+        let d: Dynamic = serde_json::from_value(manifest.clone()).unwrap();
+        assert_eq!(*d, manifest);
+        assert_eq!(serde_json::to_value(&d).unwrap(), manifest);
+        assert!(Dynamic(Value::from(1)) < Dynamic(Value::from(2)));
+    }
+
+    #[test]
+    fn test_dynamic_decode() {
+        let values = Dynamic(serde_json::json!({"a": "x", "b": null}));
+        let decoded: BTreeMap<String, Option<String>> = values.decode().unwrap();
+        assert_eq!(decoded["a"].as_deref(), Some("x"));
+        assert!(values.decode::<Vec<String>>().is_err());
+    }
+
+    #[test]
+    fn test_meta_argument_shapes() {
+        for json in &["3", r#""${length(var.zones)}""#] {
+            let count: Count = serde_json::from_str(json).unwrap();
+            assert_eq!(&serde_json::to_string(&count).unwrap(), json);
+        }
+        assert_eq!(serde_json::from_str::<Count>("2").unwrap(), Count::from(2));
+        for json in &[r#"{"a":{"size":1}}"#, r#""${toset(var.names)}""#] {
+            let for_each: ForEach = serde_json::from_str(json).unwrap();
+            assert_eq!(&serde_json::to_string(&for_each).unwrap(), json);
+        }
+        let for_each: ForEach = serde_json::from_str(r#"["a","b\"${c}"]"#).unwrap();
+        assert!(matches!(&for_each, ForEach::Set(set) if set.len() == 2));
+        assert_eq!(
+            serde_json::to_string(&for_each).unwrap(),
+            r#""${toset([\"a\", \"b\\\"$${c}\"])}""#
+        );
+        let for_each: ForEach = serde_json::from_str(r#"{"a": 1}"#).unwrap();
+        assert!(matches!(for_each, ForEach::Map(_)));
+        for json in &[r#""all""#, r#"["tags","ami"]"#] {
+            let ignore_changes: IgnoreChanges = serde_json::from_str(json).unwrap();
+            assert_eq!(&serde_json::to_string(&ignore_changes).unwrap(), json);
+        }
+        assert_eq!(
+            serde_json::from_str::<IgnoreChanges>(r#""all""#).unwrap(),
+            IgnoreChanges::All
+        );
+        assert!(serde_json::from_str::<IgnoreChanges>(r#""tags""#).is_err());
+    }
+
+    #[test]
+    fn test_expr_literals_and_expressions() {
+        let replicas: Expr<i64> = serde_json::from_str(r#""${var.replicas}""#).unwrap();
+        assert_eq!(replicas, Expr::interpolate("var.replicas"));
+        assert_eq!(
+            serde_json::from_str::<Expr<i64>>("3").unwrap(),
+            Expr::from(3)
+        );
+        // strings which are neither templates nor literals are rejected
+        assert!(serde_json::from_str::<Expr<bool>>(r#""true""#).is_err());
+        assert!(serde_json::from_str::<Expr<i64>>(r#""abc""#).is_err());
+
+        let name = Expr::from("${literal}".to_owned());
+        let json = serde_json::to_string(&name).unwrap();
+        assert_eq!(json, r#""$${literal}""#);
+        assert_eq!(serde_json::from_str::<Expr<String>>(&json).unwrap(), name);
+
+        // strings nested in literals are templates, written back untouched
+        let json = r#"{"Name":"${var.name}","Tier":"web"}"#;
+        let tags: Expr<BTreeMap<String, String>> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            tags.literal()
+                .and_then(|t| t.get("Name"))
+                .map(String::as_str),
+            Some("${var.name}")
+        );
+        assert_eq!(serde_json::to_string(&tags).unwrap(), json);
+
+        // elements wrapped in `Expr` may hold expressions of their own
+        let json = r#"["${var.a}",2]"#;
+        let ports: Expr<Vec<Expr<i64>>> = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            ports,
+            Expr::from(vec![Expr::interpolate("var.a"), Expr::from(2)])
+        );
+        assert_eq!(serde_json::to_string(&ports).unwrap(), json);
+        assert!(serde_json::from_str::<Expr<i64>>("[1]").is_err());
+        assert_eq!(
+            String::from(Expr::<String>::interpolate("aws_instance.web.id")),
+            "${aws_instance.web.id}"
+        );
+    }
+
+    #[test]
+    fn test_resource_metadata() {
+        struct Instance;
+        impl Resource for Instance {
+            const TYPE_NAME: &'static str = "aws_instance";
+            const KIND: ResourceKind = ResourceKind::Resource;
+            const PROVIDER: &'static str = "aws";
+            const SCHEMA_VERSION: i64 = 1;
+            const ATTRIBUTES: &'static [AttributeSchema] = &[AttributeSchema {
+                name: "id",
+                required: false,
+                optional: true,
+                computed: true,
+                sensitive: false,
+                deprecated: false,
+            }];
+        }
+
+        assert!(Instance::attribute("id").is_some_and(|a| a.computed));
+        assert!(Instance::attribute("ami").is_none());
+    }
+
+    #[test]
+    fn test_provider_ref_round_trip() {
+        let west: ProviderRef = serde_json::from_str(r#""aws.west""#).unwrap();
+        assert_eq!(west, ProviderRef::aliased("aws", "west"));
+        assert_eq!(serde_json::to_string(&west).unwrap(), r#""aws.west""#);
+        assert_eq!(ProviderRef::new("aws").to_string(), "aws");
+    }
+
+    #[test]
+    fn test_set_skips_duplicates() {
+        let set: Set<String> = serde_json::from_str(r#"["a", "b", "a"]"#).unwrap();
+        assert_eq!(set.len(), 2);
+        assert_eq!(serde_json::to_string(&set).unwrap(), r#"["a","b"]"#);
+    }
+
+    #[test]
+    fn test_sensitive_redacts_debug() {
+        let token: Sensitive<String> = serde_json::from_str(r#""s3cr3t""#).unwrap();
+        assert_eq!(token.as_str(), "s3cr3t");
+        assert_eq!(format!("{:?}", Some(&token)), "Some(<sensitive>)");
+        assert_eq!(serde_json::to_string(&token).unwrap(), r#""s3cr3t""#);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn test_secret_redacts_debug() {
+        let token: Secret<String> = serde_json::from_str(r#""s3cr3t""#).unwrap();
+        assert_eq!(format!("{:?}", token), "<sensitive>");
+        assert_eq!(serde_json::to_string(&token).unwrap(), r#""s3cr3t""#);
+    }
+
+    #[test]
+    fn test_deserialize_object_and_array_forms() {
+        #[derive(Debug, Deserialize)]
+        struct Root {
+            #[serde(default, deserialize_with = "deserialize_roots")]
+            resource: Option<Vec<BTreeMap<String, Value>>>,
+            #[serde(default, deserialize_with = "deserialize_block")]
+            timeouts: Option<BTreeMap<String, String>>,
+            #[serde(default, deserialize_with = "deserialize_blocks")]
+            tag: Vec<BTreeMap<String, String>>,
+            #[serde(default, deserialize_with = "deserialize_labeled_blocks")]
+            named: Vec<BTreeMap<String, Vec<i64>>>,
+            #[serde(default, deserialize_with = "deserialize_tagged_blocks")]
+            provisioner: Vec<BTreeMap<String, i64>>,
+        }
+
+        let object_form: Root = serde_json::from_str(
+            r#"{"resource": {"a": 1, "b": 2}, "timeouts": [{"create": "1m"}], "tag": {"k": "v"}, "named": {"x": 1}, "provisioner": {"a": 1, "b": 2}}"#,
+        )
+        .unwrap();
+        let array_form: Root = serde_json::from_str(
+            r#"{"resource": [{"a": 1}, {"b": 2}], "timeouts": {"create": "1m"}, "tag": [{"k": "v"}], "named": [{"x": [1]}], "provisioner": [{"a": 1}, {"b": 2}]}"#,
+        )
+        .unwrap();
+        for root in &[object_form, array_form] {
+            assert_eq!(root.resource.as_ref().map(Vec::len), Some(2));
+            assert_eq!(
+                root.timeouts.as_ref().map(|t| t["create"].as_str()),
+                Some("1m")
+            );
+            assert_eq!(root.tag.len(), 1);
+            assert_eq!(root.named[0]["x"], vec![1]);
+            assert_eq!(root.provisioner.len(), 2);
+        }
+        let empty: Root = serde_json::from_str("{}").unwrap();
+        assert!(empty.resource.is_none() && empty.timeouts.is_none());
+    }
+
+    #[test]
+    fn test_deserialize_keyed_object() {
+        #[derive(Debug, Default, Deserialize)]
+        struct Resources {
+            #[serde(default, deserialize_with = "deserialize_labels")]
+            a: Option<BTreeMap<String, BTreeMap<String, i64>>>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Root {
+            #[serde(default, deserialize_with = "deserialize_object")]
+            resource: Resources,
+        }
+
+        let object_form: Root =
+            serde_json::from_str(r#"{"resource": {"a": {"x": {"n": 1}, "y": [{"n": 2}]}}}"#)
+                .unwrap();
+        let array_form: Root = serde_json::from_str(
+            r#"{"resource": [{"a": {"x": {"n": 1}}}, {"a": {"y": [{"n": 2}]}}]}"#,
+        )
+        .unwrap();
+        let nested_array_form: Root = serde_json::from_str(
+            r#"{"resource": [{"a": [{"x": [{"n": 1}]}]}, {"a": [{"y": [{"n": 2}]}]}]}"#,
+        )
+        .unwrap();
+        for root in &[object_form, array_form, nested_array_form] {
+            let a = root.resource.a.as_ref().unwrap();
+            assert_eq!((a["x"]["n"], a["y"]["n"]), (1, 2));
+        }
+        let empty: Root = serde_json::from_str("{}").unwrap();
+        assert!(empty.resource.a.is_none());
+
+        // unlabeled blocks of the same type (e.g. providers) are not merged
+        #[derive(Debug, Default, Deserialize)]
+        struct Providers {
+            #[serde(default, deserialize_with = "deserialize_blocks")]
+            test_provider: Option<Vec<BTreeMap<String, String>>>,
+        }
+        #[derive(Debug, Deserialize)]
+        struct Config {
+            #[serde(default, deserialize_with = "deserialize_object")]
+            provider: Providers,
+        }
+        let config: Config = serde_json::from_str(
+            r#"{"provider": [{"test_provider": {"base_url": "https://a"}}, {"test_provider": {"alias": "west", "base_url": "https://b"}}]}"#,
+        )
+        .unwrap();
+        let providers = config.provider.test_provider.unwrap();
+        assert_eq!(providers.len(), 2);
+        assert_eq!(providers[0].get("alias"), None);
+        assert_eq!(providers[0]["base_url"], "https://a");
+        assert_eq!(providers[1]["alias"], "west");
+
+        // blocks keyed by label (e.g. `selector` blocks of map nesting mode)
+        for json in &[r#"[{"k": {"n": 1}}]"#, r#"{"k": [{"n": 1}]}"#] {
+            let mut de = serde_json::Deserializer::from_str(json);
+            let selector: BTreeMap<String, BTreeMap<String, i64>> =
+                deserialize_labels(&mut de).unwrap();
+            assert_eq!(selector["k"]["n"], 1);
+        }
+    }
+}
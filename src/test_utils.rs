@@ -2,7 +2,8 @@
     unused_imports,
     non_snake_case,
     non_camel_case_types,
-    non_upper_case_globals
+    non_upper_case_globals,
+    deprecated
 )]
 use serde::{Deserialize, Serialize};
 use serde_bytes::ByteBuf as Bytes;
@@ -10,25 +11,33 @@ use std::collections::BTreeMap as Map;
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub struct config {
+    #[serde(default, deserialize_with = "crate::runtime::deserialize_roots")]
     pub data: Option<Vec<datasource_root>>,
+    #[serde(default, deserialize_with = "crate::runtime::deserialize_roots")]
     pub provider: Option<Vec<provider_root>>,
+    #[serde(default, deserialize_with = "crate::runtime::deserialize_roots")]
     pub resource: Option<Vec<resource_root>>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum datasource_root {
+    #[serde(deserialize_with = "crate::runtime::deserialize_labeled_blocks")]
     test_data_source_a(Vec<Map<String, Vec<test_data_source_a_details>>>),
+    #[serde(deserialize_with = "crate::runtime::deserialize_labeled_blocks")]
     test_data_source_b(Vec<Map<String, Vec<test_data_source_b_details>>>),
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum provider_root {
+    #[serde(deserialize_with = "crate::runtime::deserialize_blocks")]
     test_provider(Vec<test_provider_details>),
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]
 pub enum resource_root {
+    #[serde(deserialize_with = "crate::runtime::deserialize_labeled_blocks")]
     test_resource_a(Vec<Map<String, Vec<test_resource_a_details>>>),
+    #[serde(deserialize_with = "crate::runtime::deserialize_labeled_blocks")]
     test_resource_b(Vec<Map<String, Vec<test_resource_b_details>>>),
 }
 
@@ -38,7 +47,8 @@ pub struct test_data_source_a_details {
     pub id: Option<String>,
     pub name: String,
     pub users: Option<Vec<String>>,
-    pub datasource_a_type: Option<Vec<test_data_source_a_data_source_block_type_datasource_a_type>>,
+    #[serde(default, deserialize_with = "crate::runtime::deserialize_blocks")]
+    pub datasource_a_type: Vec<test_data_source_a_data_source_block_type_datasource_a_type>,
 }
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Serialize, Deserialize)]